@@ -30,6 +30,127 @@ macro_rules! gen_overloads {
         gen_overloads_inner!(Sub, sub, $T);
         gen_overloads_inner!(Mul, mul, $T);
         gen_overloads_inner!(Div, div, $T);
+        gen_overloads_inner!(Rem, rem, $T);
+    }
+}
+
+macro_rules! unary_fn {
+    ($meth:ident, $meth_round:ident, $meth_ternary:ident, $func:ident) => {
+        #[inline]
+        pub fn $meth(&self) -> Mpfr {
+            self.$meth_round(Round::Nearest)
+        }
+
+        #[inline]
+        pub fn $meth_round(&self, rnd: Round) -> Mpfr {
+            self.$meth_ternary(rnd).0
+        }
+
+        pub fn $meth_ternary(&self, rnd: Round) -> (Mpfr, Ordering) {
+            unsafe {
+                let mut res = Mpfr::new2(self.get_prec());
+                let t = $func(&mut res.mpfr, &self.mpfr, rnd);
+                (res, ternary(t))
+            }
+        }
+    }
+}
+
+macro_rules! round_fn {
+    ($meth:ident, $meth_ternary:ident, $func:ident) => {
+        #[inline]
+        pub fn $meth(&self) -> Mpfr {
+            self.$meth_ternary().0
+        }
+
+        pub fn $meth_ternary(&self) -> (Mpfr, Ordering) {
+            unsafe {
+                let mut res = Mpfr::new2(self.get_prec());
+                let t = $func(&mut res.mpfr, &self.mpfr);
+                (res, ternary(t))
+            }
+        }
+    }
+}
+
+macro_rules! rint_fn {
+    ($meth:ident, $meth_ternary:ident, $func:ident) => {
+        #[inline]
+        pub fn $meth(&self, precision: usize, rnd: Round) -> Mpfr {
+            self.$meth_ternary(precision, rnd).0
+        }
+
+        pub fn $meth_ternary(&self, precision: usize, rnd: Round) -> (Mpfr, Ordering) {
+            unsafe {
+                let mut res = Mpfr::new2(precision);
+                let t = $func(&mut res.mpfr, &self.mpfr, rnd);
+                (res, ternary(t))
+            }
+        }
+    }
+}
+
+macro_rules! const_fn {
+    ($meth:ident, $meth_round:ident, $meth_ternary:ident, $func:ident) => {
+        #[inline]
+        pub fn $meth(precision: usize) -> Mpfr {
+            Mpfr::$meth_round(precision, Round::Nearest)
+        }
+
+        #[inline]
+        pub fn $meth_round(precision: usize, rnd: Round) -> Mpfr {
+            Mpfr::$meth_ternary(precision, rnd).0
+        }
+
+        pub fn $meth_ternary(precision: usize, rnd: Round) -> (Mpfr, Ordering) {
+            unsafe {
+                let mut res = Mpfr::new2(precision);
+                let t = $func(&mut res.mpfr, rnd);
+                (res, ternary(t))
+            }
+        }
+    }
+}
+
+macro_rules! random_fn {
+    ($meth:ident, $meth_round:ident, $meth_ternary:ident, $func:ident) => {
+        #[inline]
+        pub fn $meth(precision: usize, state: &mut RandState) -> Mpfr {
+            Mpfr::$meth_round(precision, state, Round::Nearest)
+        }
+
+        #[inline]
+        pub fn $meth_round(precision: usize, state: &mut RandState, rnd: Round) -> Mpfr {
+            Mpfr::$meth_ternary(precision, state, rnd).0
+        }
+
+        pub fn $meth_ternary(precision: usize,
+                             state: &mut RandState,
+                             rnd: Round)
+                             -> (Mpfr, Ordering) {
+            unsafe {
+                let mut res = Mpfr::new2(precision);
+                let t = $func(&mut res.mpfr, &mut state.state, rnd);
+                (res, ternary(t))
+            }
+        }
+    }
+}
+
+macro_rules! try_from_fits {
+    ($T:ident, $fits:ident, $get:ident) => {
+        impl<'a> TryFrom<&'a Mpfr> for $T {
+            type Error = TryFromMpfrError;
+            fn try_from(x: &'a Mpfr) -> Result<$T, TryFromMpfrError> {
+                x.check_integer()?;
+                unsafe {
+                    if $fits(&x.mpfr, Round::Zero) == 0 {
+                        return Err(TryFromMpfrError::Overflow);
+                    }
+                    Ok($get(&x.mpfr, Round::Zero) as $T)
+                }
+            }
+        }
     }
 }
 
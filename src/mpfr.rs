@@ -1,32 +1,44 @@
 use gmp::mpf::{Mpf, mpf_ptr, mpf_srcptr};
-use gmp::mpq::{Mpq, mpq_srcptr};
+use gmp::mpq::{Mpq, mpq_ptr, mpq_srcptr};
 use gmp::mpz::{Mpz, mpz_ptr, mpz_srcptr};
-use libc::{c_char, c_int, c_ulong, c_long, c_double, c_void, size_t};
+use gmp::rand::{gmp_randstate_struct, gmp_randstate_t};
+use libc::{c_char, c_int, c_uint, c_ulong, c_long, c_float, c_double, c_void, size_t};
 use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
-use std::ffi::CStr;
-use std::cmp::{Eq, PartialEq, Ord, PartialOrd, Ordering};
+use std::cmp::{PartialEq, PartialOrd, Ordering};
 use std::cmp;
-use std::convert::{From, Into};
+use std::convert::{From, Into, TryFrom};
+use std::error::Error;
 use std::ffi::CString;
 use std::fmt;
-use std::mem::uninitialized;
-use std::ops::{Add, Sub, Mul, Div, Neg};
-use std::str;
+use std::marker::PhantomData;
+use std::mem::{MaybeUninit, uninitialized};
+use std::iter::{Sum, Product};
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, Shl, Shr, BitAnd, BitOr, Not};
 use std::ptr;
 
 type mpfr_prec_t = c_long;
 type mpfr_sign_t = c_int;
 type mpfr_exp_t = c_long;
+type mpfr_flags_t = c_uint;
 
 #[repr(C)]
-pub enum mpfr_rnd_t {
-    MPFR_RNDN = 0, // round to nearest, with ties to even
-    MPFR_RNDZ, // round toward zero
-    MPFR_RNDU, // round toward +Inf
-    MPFR_RNDD, // round toward -Inf
-    MPFR_RNDA, // round away from zero
-    MPFR_RNDF, // faithful rounding (not implemented yet)
-    MPFR_RNDNA = -1, // round to nearest, with ties away from zero (mpfr_rouund)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Round {
+    #[default]
+    Nearest = 0, // round to nearest, with ties to even
+    Zero, // round toward zero
+    Up, // round toward +Inf
+    Down, // round toward -Inf
+    AwayZero, // round away from zero
+    Faithful, // faithful rounding
+}
+
+pub type mpfr_rnd_t = Round;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sign {
+    Negative,
+    Positive,
 }
 
 #[repr(C)]
@@ -56,6 +68,7 @@ extern {
     fn mpfr_set_ui(rop: mpfr_ptr, op: c_ulong, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_set_si(rop: mpfr_ptr, op: c_long, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_set_d(rop: mpfr_ptr, op: c_double, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_set_flt(rop: mpfr_ptr, op: c_float, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_set_z(rop: mpfr_ptr, op: mpz_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_set_q(rop: mpfr_ptr, op: mpq_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_set_f(rop: mpfr_ptr, op: mpf_srcptr, rnd: mpfr_rnd_t) -> c_int;
@@ -65,21 +78,46 @@ extern {
     fn mpfr_set_nan(x: mpfr_ptr);
     fn mpfr_set_inf(x: mpfr_ptr, sign: c_int);
     fn mpfr_set_zero(x: mpfr_ptr, sign: c_int);
+    fn mpfr_setsign(rop: mpfr_ptr, op: mpfr_srcptr, s: c_int, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_copysign(rop: mpfr_ptr, op1: mpfr_srcptr, op2: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_set_str(rop: mpfr_ptr, s: *const c_char, base: c_int, rnd: mpfr_rnd_t) -> c_int;
 
     // Conversion
     fn mpfr_get_ui(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_ulong;
     fn mpfr_get_si(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_long;
     fn mpfr_get_d(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_double;
+    fn mpfr_get_flt(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_float;
     fn mpfr_get_z(rop: mpz_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_get_f(rop: mpf_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_get_q(rop: mpq_ptr, op: mpfr_srcptr);
+    fn mpfr_get_z_2exp(rop: mpz_ptr, op: mpfr_srcptr) -> mpfr_exp_t;
+    fn mpfr_fits_ulong_p(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_fits_slong_p(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_fits_uint_p(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_fits_sint_p(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_fits_ushort_p(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_fits_sshort_p(op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
 
     // Comparison
     fn mpfr_cmp(op1: mpfr_srcptr, op2: mpfr_srcptr) -> c_int;
+    fn mpfr_cmpabs(op1: mpfr_srcptr, op2: mpfr_srcptr) -> c_int;
     fn mpfr_cmp_ui(op1: mpfr_srcptr, op2: c_ulong) -> c_int;
+    fn mpfr_cmp_si(op1: mpfr_srcptr, op2: c_long) -> c_int;
+    fn mpfr_cmp_ui_2exp(op1: mpfr_srcptr, op2: c_ulong, e: mpfr_exp_t) -> c_int;
+    fn mpfr_cmp_si_2exp(op1: mpfr_srcptr, op2: c_long, e: mpfr_exp_t) -> c_int;
+    fn mpfr_equal_p(op1: mpfr_srcptr, op2: mpfr_srcptr) -> c_int;
+    fn mpfr_unordered_p(op1: mpfr_srcptr, op2: mpfr_srcptr) -> c_int;
+    fn mpfr_nan_p(op: mpfr_srcptr) -> c_int;
+    fn mpfr_inf_p(op: mpfr_srcptr) -> c_int;
+    fn mpfr_number_p(op: mpfr_srcptr) -> c_int;
+    fn mpfr_zero_p(op: mpfr_srcptr) -> c_int;
+    fn mpfr_regular_p(op: mpfr_srcptr) -> c_int;
+    fn mpfr_integer_p(op: mpfr_srcptr) -> c_int;
+    fn mpfr_signbit(op: mpfr_srcptr) -> c_int;
 
     // Arithmetic
     fn mpfr_add(rop: mpfr_ptr, op1: mpfr_srcptr, op2: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_add_ui(rop: mpfr_ptr, op1: mpfr_srcptr, op2: c_ulong, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_add_d(rop: mpfr_ptr, op1: mpfr_srcptr, op2: c_double, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_add_si(rop: mpfr_ptr, op1: mpfr_srcptr, op2: c_long, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_sub(rop: mpfr_ptr, op1: mpfr_srcptr, op2: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
@@ -96,11 +134,66 @@ extern {
     fn mpfr_d_div(rop: mpfr_ptr, op1: c_double, op2: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_si_div(rop: mpfr_ptr, op1: c_long, op2: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_neg(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_mul_2si(rop: mpfr_ptr, op: mpfr_srcptr, n: c_long, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_div_2si(rop: mpfr_ptr, op: mpfr_srcptr, n: c_long, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_fmod(rop: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_remainder(rop: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_remquo(rop: mpfr_ptr,
+                   q: *mut c_long,
+                   x: mpfr_srcptr,
+                   y: mpfr_srcptr,
+                   rnd: mpfr_rnd_t)
+                   -> c_int;
+    fn mpfr_fma(rop: mpfr_ptr,
+                op1: mpfr_srcptr,
+                op2: mpfr_srcptr,
+                op3: mpfr_srcptr,
+                rnd: mpfr_rnd_t)
+                -> c_int;
+    fn mpfr_fms(rop: mpfr_ptr,
+                op1: mpfr_srcptr,
+                op2: mpfr_srcptr,
+                op3: mpfr_srcptr,
+                rnd: mpfr_rnd_t)
+                -> c_int;
+    fn mpfr_fmma(rop: mpfr_ptr,
+                 op1: mpfr_srcptr,
+                 op2: mpfr_srcptr,
+                 op3: mpfr_srcptr,
+                 op4: mpfr_srcptr,
+                 rnd: mpfr_rnd_t)
+                 -> c_int;
+    fn mpfr_fmms(rop: mpfr_ptr,
+                 op1: mpfr_srcptr,
+                 op2: mpfr_srcptr,
+                 op3: mpfr_srcptr,
+                 op4: mpfr_srcptr,
+                 rnd: mpfr_rnd_t)
+                 -> c_int;
+    fn mpfr_hypot(rop: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_sum(rop: mpfr_ptr, tab: *const mpfr_srcptr, n: c_ulong, rnd: mpfr_rnd_t) -> c_int;
+    #[cfg(feature = "mpfr_4_1")]
+    fn mpfr_dot(rop: mpfr_ptr,
+                a: *const mpfr_srcptr,
+                b: *const mpfr_srcptr,
+                n: c_ulong,
+                rnd: mpfr_rnd_t)
+                -> c_int;
 
     // Rounding
     fn mpfr_floor(rop: mpfr_ptr, op: mpfr_srcptr) -> c_int;
     fn mpfr_ceil(rop: mpfr_ptr, op: mpfr_srcptr) -> c_int;
     fn mpfr_round(rop: mpfr_ptr, op: mpfr_srcptr) -> c_int;
+    fn mpfr_roundeven(rop: mpfr_ptr, op: mpfr_srcptr) -> c_int;
+    fn mpfr_trunc(rop: mpfr_ptr, op: mpfr_srcptr) -> c_int;
+    fn mpfr_rint(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_rint_floor(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_rint_ceil(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_rint_round(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_rint_roundeven(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_rint_trunc(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_frac(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_modf(iop: mpfr_ptr, fop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
 
     // Functions
     fn mpfr_sqrt(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
@@ -110,39 +203,196 @@ extern {
     fn mpfr_abs(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_exp(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_log(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_log_ui(rop: mpfr_ptr, op: c_ulong, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_log2(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_log10(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_log1p(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_exp2(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_exp10(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_expm1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    #[cfg(feature = "mpfr_4_2")]
+    fn mpfr_log2p1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    #[cfg(feature = "mpfr_4_2")]
+    fn mpfr_log10p1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    #[cfg(feature = "mpfr_4_2")]
+    fn mpfr_exp2m1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    #[cfg(feature = "mpfr_4_2")]
+    fn mpfr_exp10m1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    #[cfg(feature = "mpfr_4_2")]
+    fn mpfr_compound_si(rop: mpfr_ptr, op: mpfr_srcptr, n: c_long, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_gamma(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
     fn mpfr_lngamma(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
-    fn mpfr_lgamma(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_lgamma(rop: mpfr_ptr, signp: *mut c_int, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_digamma(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_beta(rop: mpfr_ptr, op1: mpfr_srcptr, op2: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_erf(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_erfc(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_zeta(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_zeta_ui(rop: mpfr_ptr, op: c_ulong, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_eint(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_li2(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_ai(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_j0(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_j1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_jn(rop: mpfr_ptr, n: c_long, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_y0(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_y1(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_yn(rop: mpfr_ptr, n: c_long, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+
+    // Constants
+    fn mpfr_const_pi(rop: mpfr_ptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_const_log2(rop: mpfr_ptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_const_euler(rop: mpfr_ptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_const_catalan(rop: mpfr_ptr, rnd: mpfr_rnd_t) -> c_int;
+
+    // Trigonometric functions
+    fn mpfr_sin(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_cos(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_tan(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_sec(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_csc(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_cot(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_sin_cos(sop: mpfr_ptr, cop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_asin(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_acos(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_atan(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_atan2(rop: mpfr_ptr, y: mpfr_srcptr, x: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+
+    // Hyperbolic functions
+    fn mpfr_sinh(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_cosh(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_tanh(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_sech(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_csch(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_coth(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_sinh_cosh(sop: mpfr_ptr, cop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_asinh(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_acosh(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_atanh(rop: mpfr_ptr, op: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+
+    // Exponent
+    fn mpfr_get_exp(x: mpfr_srcptr) -> mpfr_exp_t;
+    fn mpfr_set_exp(x: mpfr_ptr, e: mpfr_exp_t) -> c_int;
+    fn mpfr_frexp(exp: *mut mpfr_exp_t, y: mpfr_ptr, x: mpfr_srcptr, rnd: mpfr_rnd_t) -> c_int;
+
+    // Neighbours
+    fn mpfr_nextabove(x: mpfr_ptr);
+    fn mpfr_nextbelow(x: mpfr_ptr);
+    fn mpfr_nexttoward(x: mpfr_ptr, y: mpfr_srcptr);
+
+    // Exponent range
+    fn mpfr_get_emin() -> mpfr_exp_t;
+    fn mpfr_get_emax() -> mpfr_exp_t;
+    fn mpfr_set_emin(exp: mpfr_exp_t) -> c_int;
+    fn mpfr_set_emax(exp: mpfr_exp_t) -> c_int;
+    fn mpfr_check_range(x: mpfr_ptr, t: c_int, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_subnormalize(x: mpfr_ptr, t: c_int, rnd: mpfr_rnd_t) -> c_int;
+
+    // Exceptions
+    fn mpfr_flags_clear(mask: mpfr_flags_t);
+    fn mpfr_flags_set(mask: mpfr_flags_t);
+    fn mpfr_flags_test(mask: mpfr_flags_t) -> mpfr_flags_t;
+    fn mpfr_flags_save() -> mpfr_flags_t;
+    fn mpfr_flags_restore(flags: mpfr_flags_t, mask: mpfr_flags_t);
+
+    // Random numbers
+    fn mpfr_urandomb(rop: mpfr_ptr, state: gmp_randstate_t) -> c_int;
+    fn mpfr_urandom(rop: mpfr_ptr, state: gmp_randstate_t, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_nrandom(rop: mpfr_ptr, state: gmp_randstate_t, rnd: mpfr_rnd_t) -> c_int;
+    fn mpfr_erandom(rop: mpfr_ptr, state: gmp_randstate_t, rnd: mpfr_rnd_t) -> c_int;
 
     // Formatted output
-    fn mpfr_snprintf(buffer: *const c_char, length: size_t, string: *const u8, ...) -> c_int;
+    fn mpfr_snprintf(buffer: *mut c_char, length: size_t, template: *const c_char, ...) -> c_int;
+}
+
+// rust-gmp binds these privately, so RandState needs its own declarations
+#[link(name = "gmp")]
+extern "C" {
+    fn __gmp_randinit_default(state: gmp_randstate_t);
+    fn __gmp_randinit_mt(state: gmp_randstate_t);
+    fn __gmp_randinit_set(state: gmp_randstate_t, op: *const gmp_randstate_struct);
+    fn __gmp_randclear(state: gmp_randstate_t);
+    fn __gmp_randseed(state: gmp_randstate_t, seed: mpz_srcptr);
+    fn __gmp_randseed_ui(state: gmp_randstate_t, seed: c_ulong);
 }
 
 pub struct Mpfr {
     pub mpfr: mpfr_struct,
 }
 
+// Converts MPFR's ternary return value into the ordering of the rounded
+// result relative to the exact one
+#[inline]
+fn ternary(t: c_int) -> Ordering {
+    t.cmp(&0)
+}
+
+// Decodes the combined ternary value of functions computing two results at
+// once (e.g. mpfr_sin_cos), which is s + 4c with 1 meaning "rounded up" and
+// 2 meaning "rounded down"
+fn ternary_pair(t: c_int) -> (Ordering, Ordering) {
+    fn decode(t: c_int) -> Ordering {
+        match t {
+            1 => Ordering::Greater,
+            2 => Ordering::Less,
+            _ => Ordering::Equal,
+        }
+    }
+    (decode(t & 3), decode(t >> 2))
+}
+
 impl fmt::Debug for Mpfr {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self, fmt)
     }
 }
 
+// Without a precision, values are printed in scientific notation with as
+// many digits as needed to read them back exactly. With a precision, they are
+// printed in fixed notation with that many digits after the point. Width,
+// fill, alignment and the sign flags work like they do for f64.
 impl fmt::Display for Mpfr {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if !self.is_finite() {
+            return fmt::Display::fmt(&self.to_f64_round(Round::Nearest), fmt);
+        }
+
+        let s = match fmt.precision() {
+            Some(digits) => self.snprintf(&format!("%.{}Rf", digits))?,
+            None => {
+                // %Re pads the significand with zeros up to the full precision
+                let s = self.snprintf("%Re")?;
+                let e = s.find('e').unwrap_or(s.len());
+                let significand = s[..e].trim_end_matches('0').trim_end_matches('.');
+                format!("{}{}", significand, &s[e..])
+            }
+        };
+        if let Some(digits) = s.strip_prefix('-') {
+            fmt.pad_integral(false, "", digits)
+        } else {
+            fmt.pad_integral(true, "", &s)
+        }
+    }
+}
+
+impl Mpfr {
+    fn snprintf(&self, template: &str) -> Result<String, fmt::Error> {
+        let template = CString::new(template).unwrap();
         unsafe {
-            let length = mpfr_snprintf(ptr::null(), 0, b"%.Re\0".as_ptr(), &self.mpfr);
+            let length = mpfr_snprintf(ptr::null_mut(),
+                                       0,
+                                       template.as_ptr(),
+                                       &self.mpfr as mpfr_srcptr);
             if length < 0 {
-                // Maybe fmt.write_str("@uninnitialized@")
-                return Ok(())
+                return Err(fmt::Error);
             }
-            let buff: Vec<c_char> = Vec::with_capacity((length + 1) as usize);
-            mpfr_snprintf(buff.as_ptr(),
-                          (length + 1) as size_t,
-                          b"%.Re\0".as_ptr(),
-                          &self.mpfr);
-            let s = CStr::from_ptr(buff.as_ptr());
-            fmt.write_str(str::from_utf8_unchecked(s.to_bytes()))
+            let mut buff = vec![0u8; length as usize + 1];
+            mpfr_snprintf(buff.as_mut_ptr() as *mut c_char,
+                          buff.len() as size_t,
+                          template.as_ptr(),
+                          &self.mpfr as mpfr_srcptr);
+            buff.truncate(length as usize);
+            Ok(String::from_utf8_unchecked(buff))
         }
     }
 }
@@ -182,34 +432,29 @@ impl Mpfr {
     }
 
     pub fn new_from_str<T: Into<Vec<u8>>>(s: T, base: usize) -> Option<Mpfr> {
-        let c_string = match CString::new(s) {
-            Ok(c_string) => c_string,
-            Err(..) => return None,
-        };
-        unsafe {
-            let mut mpfr = Mpfr::new();
-            if mpfr_set_str(&mut mpfr.mpfr,
-                            c_string.as_ptr(),
-                            base as c_int,
-                            mpfr_rnd_t::MPFR_RNDN) == 0 {
-                Some(mpfr)
-            } else {
-                None
-            }
-        }
+        Mpfr::new_from_str_round(s, base, Round::Nearest)
+    }
+
+    pub fn new_from_str_round<T: Into<Vec<u8>>>(s: T, base: usize, rnd: Round) -> Option<Mpfr> {
+        Mpfr::new2_from_str_round(Mpfr::get_default_prec(), s, base, rnd)
     }
 
     pub fn new2_from_str<T: Into<Vec<u8>>>(precision: usize, s: T, base: usize) -> Option<Mpfr> {
+        Mpfr::new2_from_str_round(precision, s, base, Round::Nearest)
+    }
+
+    pub fn new2_from_str_round<T: Into<Vec<u8>>>(precision: usize,
+                                                 s: T,
+                                                 base: usize,
+                                                 rnd: Round)
+                                                 -> Option<Mpfr> {
         let c_string = match CString::new(s) {
             Ok(c_string) => c_string,
             Err(..) => return None,
         };
         unsafe {
             let mut mpfr = Mpfr::new2(precision);
-            if mpfr_set_str(&mut mpfr.mpfr,
-                            c_string.as_ptr(),
-                            base as c_int,
-                            mpfr_rnd_t::MPFR_RNDN) == 0 {
+            if mpfr_set_str(&mut mpfr.mpfr, c_string.as_ptr(), base as c_int, rnd) == 0 {
                 Some(mpfr)
             } else {
                 None
@@ -218,18 +463,136 @@ impl Mpfr {
     }
 
     pub fn set(&mut self, other: &Mpfr) {
+        self.set_round(other, Round::Nearest);
+    }
+
+    pub fn set_round(&mut self, other: &Mpfr, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set(&mut self.mpfr, &other.mpfr, rnd))
+        }
+    }
+
+    pub fn set_i64_round(&mut self, x: i64, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set_si(&mut self.mpfr, x as c_long, rnd))
+        }
+    }
+
+    pub fn set_u64_round(&mut self, x: u64, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set_ui(&mut self.mpfr, x as c_ulong, rnd))
+        }
+    }
+
+    // MPFR has no 128-bit setters, so the high half is set exactly and the
+    // low half added to it, rounding only once
+    pub fn set_i128_round(&mut self, x: i128, rnd: Round) -> Ordering {
+        unsafe {
+            let mut hi = Mpfr::new2(128);
+            mpfr_set_si_2exp(&mut hi.mpfr, (x >> 64) as c_long, 64, Round::Nearest);
+            ternary(mpfr_add_ui(&mut self.mpfr, &hi.mpfr, x as u64 as c_ulong, rnd))
+        }
+    }
+
+    pub fn set_u128_round(&mut self, x: u128, rnd: Round) -> Ordering {
+        unsafe {
+            let mut hi = Mpfr::new2(128);
+            mpfr_set_ui_2exp(&mut hi.mpfr, (x >> 64) as c_ulong, 64, Round::Nearest);
+            ternary(mpfr_add_ui(&mut self.mpfr, &hi.mpfr, x as u64 as c_ulong, rnd))
+        }
+    }
+
+    pub fn set_f64_round(&mut self, x: f64, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set_d(&mut self.mpfr, x as c_double, rnd))
+        }
+    }
+
+    pub fn set_f32_round(&mut self, x: f32, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set_flt(&mut self.mpfr, x as c_float, rnd))
+        }
+    }
+
+    pub fn set_mpz_round(&mut self, x: &Mpz, rnd: Round) -> Ordering {
         unsafe {
-            mpfr_set(&mut self.mpfr, &other.mpfr, mpfr_rnd_t::MPFR_RNDN);
+            ternary(mpfr_set_z(&mut self.mpfr, x.inner(), rnd))
         }
     }
 
+    pub fn set_mpq_round(&mut self, x: &Mpq, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set_q(&mut self.mpfr, x.inner(), rnd))
+        }
+    }
+
+    pub fn set_mpf_round(&mut self, x: &Mpf, rnd: Round) -> Ordering {
+        unsafe {
+            ternary(mpfr_set_f(&mut self.mpfr, x.inner(), rnd))
+        }
+    }
+
+    pub fn from_i64_ternary(precision: usize, x: i64, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_i64_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_u64_ternary(precision: usize, x: u64, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_u64_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_i128_ternary(precision: usize, x: i128, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_i128_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_u128_ternary(precision: usize, x: u128, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_u128_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_f64_ternary(precision: usize, x: f64, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_f64_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_f32_ternary(precision: usize, x: f32, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_f32_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_mpz_ternary(precision: usize, x: &Mpz, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_mpz_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_mpq_ternary(precision: usize, x: &Mpq, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_mpq_round(x, rnd);
+        (mpfr, t)
+    }
+
+    pub fn from_mpf_ternary(precision: usize, x: &Mpf, rnd: Round) -> (Mpfr, Ordering) {
+        let mut mpfr = Mpfr::new2(precision);
+        let t = mpfr.set_mpf_round(x, rnd);
+        (mpfr, t)
+    }
+
     pub fn new_u64_2exp(base: u64, exp: i32) -> Mpfr {
         unsafe {
             let mut mpfr = Mpfr::new();
             mpfr_set_ui_2exp(&mut mpfr.mpfr,
                              base as c_ulong,
                              exp as mpfr_exp_t,
-                             mpfr_rnd_t::MPFR_RNDN);
+                             Round::Nearest);
             mpfr
         }
     }
@@ -240,7 +603,7 @@ impl Mpfr {
             mpfr_set_si_2exp(&mut mpfr.mpfr,
                              base as c_long,
                              exp as mpfr_exp_t,
-                             mpfr_rnd_t::MPFR_RNDN);
+                             Round::Nearest);
             mpfr
         }
     }
@@ -251,7 +614,7 @@ impl Mpfr {
             mpfr_set_z_2exp(&mut mpfr.mpfr,
                             base.inner(),
                             exp as mpfr_exp_t,
-                            mpfr_rnd_t::MPFR_RNDN);
+                            Round::Nearest);
             mpfr
         }
     }
@@ -280,6 +643,13 @@ impl Mpfr {
         }
     }
 
+    // Constants
+
+    const_fn!(pi, pi_round, pi_ternary, mpfr_const_pi);
+    const_fn!(ln2, ln2_round, ln2_ternary, mpfr_const_log2);
+    const_fn!(euler, euler_round, euler_ternary, mpfr_const_euler);
+    const_fn!(catalan, catalan_round, catalan_ternary, mpfr_const_catalan);
+
     pub fn get_default_prec() -> usize {
         unsafe { mpfr_get_default_prec() as usize }
     }
@@ -300,250 +670,1167 @@ impl Mpfr {
         }
     }
 
-    // Rounding
+    // Classification
 
-    pub fn floor(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_floor(&mut res.mpfr, &self.mpfr);
-            res
-        }
+    pub fn is_nan(&self) -> bool {
+        unsafe { mpfr_nan_p(&self.mpfr) != 0 }
     }
 
-    pub fn ceil(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_ceil(&mut res.mpfr, &self.mpfr);
-            res
-        }
+    pub fn is_infinite(&self) -> bool {
+        unsafe { mpfr_inf_p(&self.mpfr) != 0 }
     }
 
-    pub fn round(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_round(&mut res.mpfr, &self.mpfr);
-            res
-        }
+    // Neither NaN nor infinite
+    pub fn is_finite(&self) -> bool {
+        unsafe { mpfr_number_p(&self.mpfr) != 0 }
     }
 
-    // Mathematical functions
+    pub fn is_zero(&self) -> bool {
+        unsafe { mpfr_zero_p(&self.mpfr) != 0 }
+    }
 
-    pub fn sqrt(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_sqrt(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+    // Neither NaN, infinite nor zero
+    pub fn is_regular(&self) -> bool {
+        unsafe { mpfr_regular_p(&self.mpfr) != 0 }
     }
 
-    pub fn cbrt(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_cbrt(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+    pub fn is_integer(&self) -> bool {
+        unsafe { mpfr_integer_p(&self.mpfr) != 0 }
     }
 
-    pub fn root(&self, k: u64) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_root(&mut res.mpfr,
-                      &self.mpfr,
-                      k as c_ulong,
-                      mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+    // Sign
+    //
+    // These look at the sign bit only, so they also work on zeros and NaN:
+    // -0 and -NaN are negative.
+
+    pub fn sign(&self) -> Sign {
+        if self.is_sign_negative() { Sign::Negative } else { Sign::Positive }
     }
 
-    pub fn pow(&self, other: &Mpfr) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_pow(&mut res.mpfr,
-                     &self.mpfr,
-                     &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+    pub fn is_sign_negative(&self) -> bool {
+        unsafe { mpfr_signbit(&self.mpfr) != 0 }
     }
 
-    pub fn abs(&self) -> Mpfr {
+    // self with the sign of other
+    pub fn copysign(&self, other: &Mpfr) -> Mpfr {
         unsafe {
             let mut res = Mpfr::new2(self.get_prec());
-            mpfr_abs(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
+            mpfr_copysign(&mut res.mpfr, &self.mpfr, &other.mpfr, Round::Nearest);
             res
         }
     }
 
-    pub fn exp(&self) -> Mpfr {
+    pub fn set_sign(&mut self, sign: Sign) {
         unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_exp(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
+            let neg = (sign == Sign::Negative) as c_int;
+            mpfr_setsign(&mut self.mpfr, &self.mpfr, neg, Round::Nearest);
         }
     }
 
-    pub fn log(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_log(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
+    // 1 with the sign of self (including for zeros), or NaN if self is NaN
+    pub fn signum(&self) -> Mpfr {
+        // new2 initializes to NaN
+        let mut res = Mpfr::new2(self.get_prec());
+        if !self.is_nan() {
+            res.set_i64_round(1, Round::Nearest);
+            res.set_sign(self.sign());
         }
+        res
     }
 
-    pub fn gamma(&self) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(self.get_prec());
-            mpfr_gamma(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
+    // Exponent
+    //
+    // MPFR normalizes the significand to [0.5, 1), so self is
+    // significand * 2^get_exp(). Zero, infinities and NaN have no exponent.
+
+    pub fn get_exp(&self) -> Option<i64> {
+        if self.is_regular() {
+            unsafe { Some(mpfr_get_exp(&self.mpfr) as i64) }
+        } else {
+            None
         }
     }
 
-    pub fn lngamma(&self) -> Mpfr {
+    // Returns false, leaving self unchanged, if self is not a regular number
+    // or exp is outside the current exponent range
+    pub fn set_exp(&mut self, exp: i64) -> bool {
+        unsafe { mpfr_set_exp(&mut self.mpfr, exp as mpfr_exp_t) == 0 }
+    }
+
+    // Splits self into a significand in [0.5, 1) with the sign of self, and
+    // an exponent. Zero gives exponent 0; infinities and NaN are returned
+    // unchanged, with exponent 0.
+    pub fn frexp(&self) -> (Mpfr, i64) {
         unsafe {
             let mut res = Mpfr::new2(self.get_prec());
-            mpfr_lngamma(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
+            let mut exp: mpfr_exp_t = 0;
+            mpfr_frexp(&mut exp, &mut res.mpfr, &self.mpfr, Round::Nearest);
+            (res, exp as i64)
         }
     }
 
-    pub fn lgamma(&self) -> Mpfr {
+    // self * 2^exp, which is exact unless it overflows or underflows
+    pub fn ldexp(&self, exp: i64) -> Mpfr {
+        self.ldexp_round(exp, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn ldexp_round(&self, exp: i64, rnd: Round) -> Mpfr {
+        self.ldexp_ternary(exp, rnd).0
+    }
+
+    pub fn ldexp_ternary(&self, exp: i64, rnd: Round) -> (Mpfr, Ordering) {
         unsafe {
             let mut res = Mpfr::new2(self.get_prec());
-            mpfr_lgamma(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            res
+            let t = mpfr_mul_2si(&mut res.mpfr, &self.mpfr, exp as c_long, rnd);
+            (res, ternary(t))
         }
     }
-}
 
-impl Eq for Mpfr { }
-impl PartialEq for Mpfr {
-    fn eq(&self, other: &Mpfr) -> bool {
-        unsafe { mpfr_cmp(&self.mpfr, &other.mpfr) == 0 }
+    // Neighbours
+    //
+    // The next representable value at the precision of self and in the
+    // current exponent range. NaN stays NaN.
+
+    pub fn next_up(&self) -> Mpfr {
+        let mut res = self.clone();
+        unsafe { mpfr_nextabove(&mut res.mpfr) };
+        res
     }
-}
 
-impl Ord for Mpfr {
-    fn cmp(&self, other: &Mpfr) -> Ordering {
-        let cmp = unsafe { mpfr_cmp(&self.mpfr, &other.mpfr) };
-        if cmp == 0 {
-            Ordering::Equal
-        } else if cmp > 0 {
-            Ordering::Greater
-        } else {
-            Ordering::Less
+    pub fn next_down(&self) -> Mpfr {
+        let mut res = self.clone();
+        unsafe { mpfr_nextbelow(&mut res.mpfr) };
+        res
+    }
+
+    // Returns self if it equals other, and NaN if either is NaN
+    pub fn next_toward(&self, other: &Mpfr) -> Mpfr {
+        let mut res = self.clone();
+        unsafe { mpfr_nexttoward(&mut res.mpfr, &other.mpfr) };
+        res
+    }
+
+    // Unit in the last place: the weight of the least significant bit of the
+    // significand, at the precision of self. It is never smaller than the
+    // smallest positive representable value 2^(emin-1), which is also the ulp
+    // of zero; with ExpRange::ieee this gives the subnormal spacing.
+    // Infinities give +Inf and NaN gives NaN.
+    pub fn ulp(&self) -> Mpfr {
+        match self.get_exp() {
+            Some(exp) => unsafe {
+                let exp = cmp::max(exp - self.get_prec() as i64, Mpfr::get_emin() - 1);
+                let mut res = Mpfr::new2(self.get_prec());
+                mpfr_set_ui_2exp(&mut res.mpfr, 1, exp as mpfr_exp_t, Round::Nearest);
+                res
+            },
+            None if self.is_zero() => unsafe {
+                let mut res = Mpfr::new2(self.get_prec());
+                mpfr_set_zero(&mut res.mpfr, 1);
+                mpfr_nextabove(&mut res.mpfr);
+                res
+            },
+            None if self.is_infinite() => Mpfr::inf(1),
+            None => Mpfr::nan(),
         }
     }
-}
 
-impl PartialOrd for Mpfr {
-    fn partial_cmp(&self, other: &Mpfr) -> Option<Ordering> {
-        Some(self.cmp(other))
+    // Rounding
+    //
+    // floor, ceil, round, roundeven and trunc round to an integer at the
+    // precision of self. The rint_* variants round to an integer first, then
+    // round that integer to the given precision with `rnd`.
+
+    round_fn!(floor, floor_ternary, mpfr_floor);
+    round_fn!(ceil, ceil_ternary, mpfr_ceil);
+    round_fn!(round, round_ternary, mpfr_round);
+    round_fn!(roundeven, roundeven_ternary, mpfr_roundeven);
+    round_fn!(trunc, trunc_ternary, mpfr_trunc);
+
+    rint_fn!(rint, rint_ternary, mpfr_rint);
+    rint_fn!(rint_floor, rint_floor_ternary, mpfr_rint_floor);
+    rint_fn!(rint_ceil, rint_ceil_ternary, mpfr_rint_ceil);
+    rint_fn!(rint_round, rint_round_ternary, mpfr_rint_round);
+    rint_fn!(rint_roundeven, rint_roundeven_ternary, mpfr_rint_roundeven);
+    rint_fn!(rint_trunc, rint_trunc_ternary, mpfr_rint_trunc);
+
+    unary_fn!(frac, frac_round, frac_ternary, mpfr_frac);
+
+    // Integral and fractional parts, both with the sign of self
+    pub fn modf(&self) -> (Mpfr, Mpfr) {
+        self.modf_round(Round::Nearest)
     }
-}
 
-// Conversions
+    pub fn modf_round(&self, rnd: Round) -> (Mpfr, Mpfr) {
+        let ((int, _), (frac, _)) = self.modf_ternary(rnd);
+        (int, frac)
+    }
 
-impl From<i64> for Mpfr {
-    fn from(x: i64) -> Mpfr {
+    pub fn modf_ternary(&self, rnd: Round) -> ((Mpfr, Ordering), (Mpfr, Ordering)) {
         unsafe {
-            let mut mpfr = Mpfr::new();
-            mpfr_set_si(&mut mpfr.mpfr, x as c_long, mpfr_rnd_t::MPFR_RNDN);
-            mpfr
+            let mut int = Mpfr::new2(self.get_prec());
+            let mut frac = Mpfr::new2(self.get_prec());
+            let t = mpfr_modf(&mut int.mpfr, &mut frac.mpfr, &self.mpfr, rnd);
+            let (t_int, t_frac) = ternary_pair(t);
+            ((int, t_int), (frac, t_frac))
         }
     }
-}
 
-impl From<u64> for Mpfr {
-    fn from(x: u64) -> Mpfr {
-        unsafe {
-            let mut mpfr = Mpfr::new();
-            mpfr_set_ui(&mut mpfr.mpfr, x as c_ulong, mpfr_rnd_t::MPFR_RNDN);
-            mpfr
-        }
+    // Arithmetic
+
+    #[inline]
+    pub fn add_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.add_ternary(other, rnd).0
     }
-}
 
-impl From<f64> for Mpfr {
-    fn from(x: f64) -> Mpfr {
+    pub fn add_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
         unsafe {
-            let mut mpfr = Mpfr::new();
-            mpfr_set_d(&mut mpfr.mpfr, x as c_double, mpfr_rnd_t::MPFR_RNDN);
-            mpfr
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_add(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
         }
     }
-}
 
-impl From<Mpz> for Mpfr {
-    fn from(x: Mpz) -> Mpfr {
+    #[inline]
+    pub fn sub_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.sub_ternary(other, rnd).0
+    }
+
+    pub fn sub_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
         unsafe {
-            let mut mpfr = Mpfr::new();
-            mpfr_set_z(&mut mpfr.mpfr, x.inner(), mpfr_rnd_t::MPFR_RNDN);
-            mpfr
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_sub(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
         }
     }
-}
 
-impl From<Mpq> for Mpfr {
-    fn from(x: Mpq) -> Mpfr {
+    #[inline]
+    pub fn mul_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.mul_ternary(other, rnd).0
+    }
+
+    pub fn mul_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
         unsafe {
-            let mut mpfr = Mpfr::new();
-            mpfr_set_q(&mut mpfr.mpfr, x.inner(), mpfr_rnd_t::MPFR_RNDN);
-            mpfr
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_mul(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
         }
     }
-}
 
-impl From<Mpf> for Mpfr {
-    fn from(x: Mpf) -> Mpfr {
+    #[inline]
+    pub fn div_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.div_ternary(other, rnd).0
+    }
+
+    pub fn div_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
         unsafe {
-            let mut mpfr = Mpfr::new();
-            mpfr_set_f(&mut mpfr.mpfr, x.inner(), mpfr_rnd_t::MPFR_RNDN);
-            mpfr
+            if other.is_zero() {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_div(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
         }
     }
-}
 
-impl<'a> Into<i64> for &'a Mpfr {
-    fn into(self) -> i64 {
-        unsafe { mpfr_get_si(&self.mpfr, mpfr_rnd_t::MPFR_RNDN) as i64 }
+    // Remainders
+    //
+    // rem (the % operator) truncates the quotient, like fmod in C, so the
+    // result has the sign of self. remainder and remquo round the quotient
+    // to the nearest integer instead.
+    //
+    // Like division, rem_* and % panic on a zero divisor; remainder and
+    // remquo return NaN, as MPFR does. A NaN operand gives NaN for all of
+    // them.
+
+    #[inline]
+    pub fn rem_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.rem_ternary(other, rnd).0
     }
-}
 
-impl<'a> Into<u64> for &'a Mpfr {
-    fn into(self) -> u64 {
-        unsafe { mpfr_get_ui(&self.mpfr, mpfr_rnd_t::MPFR_RNDN) as u64 }
+    pub fn rem_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            if other.is_zero() {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_fmod(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
+        }
     }
-}
 
-impl<'a> Into<f64> for &'a Mpfr {
-    fn into(self) -> f64 {
-        unsafe { mpfr_get_d(&self.mpfr, mpfr_rnd_t::MPFR_RNDN) as f64 }
+    pub fn remainder(&self, other: &Mpfr) -> Mpfr {
+        self.remainder_round(other, Round::Nearest)
     }
-}
 
-impl<'a> Into<Mpz> for &'a Mpfr {
-    fn into(self) -> Mpz {
+    #[inline]
+    pub fn remainder_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.remainder_ternary(other, rnd).0
+    }
+
+    pub fn remainder_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
         unsafe {
-            let mut result = Mpz::new();
-            mpfr_get_z(result.inner_mut(), &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            result
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_remainder(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
         }
     }
-}
 
-impl<'a> Into<Mpf> for &'a Mpfr {
-    fn into(self) -> Mpf {
+    // Like remainder, but also returns the low bits of the quotient together
+    // with its sign (at least the three lowest bits, as in C's remquo)
+    pub fn remquo(&self, other: &Mpfr) -> (Mpfr, i64) {
+        self.remquo_round(other, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn remquo_round(&self, other: &Mpfr, rnd: Round) -> (Mpfr, i64) {
+        let (res, quo, _) = self.remquo_ternary(other, rnd);
+        (res, quo)
+    }
+
+    pub fn remquo_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, i64, Ordering) {
         unsafe {
-            let mut result = Mpf::new(self.get_prec());
-            mpfr_get_f(result.inner_mut(), &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
-            result
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let mut quo: c_long = 0;
+            let t = mpfr_remquo(&mut res.mpfr, &mut quo, &self.mpfr, &other.mpfr, rnd);
+            (res, quo as i64, ternary(t))
         }
     }
-}
 
-//
-// Addition
-//
+    // Fused operations, rounded once
+
+    // self * a + b
+    pub fn mul_add(&self, a: &Mpfr, b: &Mpfr) -> Mpfr {
+        self.mul_add_round(a, b, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn mul_add_round(&self, a: &Mpfr, b: &Mpfr, rnd: Round) -> Mpfr {
+        self.mul_add_ternary(a, b, rnd).0
+    }
+
+    pub fn mul_add_ternary(&self, a: &Mpfr, b: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let prec = cmp::max(self.get_prec(), cmp::max(a.get_prec(), b.get_prec()));
+            let mut res = Mpfr::new2(prec);
+            let t = mpfr_fma(&mut res.mpfr, &self.mpfr, &a.mpfr, &b.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // self * a - b
+    pub fn mul_sub(&self, a: &Mpfr, b: &Mpfr) -> Mpfr {
+        self.mul_sub_round(a, b, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn mul_sub_round(&self, a: &Mpfr, b: &Mpfr, rnd: Round) -> Mpfr {
+        self.mul_sub_ternary(a, b, rnd).0
+    }
+
+    pub fn mul_sub_ternary(&self, a: &Mpfr, b: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let prec = cmp::max(self.get_prec(), cmp::max(a.get_prec(), b.get_prec()));
+            let mut res = Mpfr::new2(prec);
+            let t = mpfr_fms(&mut res.mpfr, &self.mpfr, &a.mpfr, &b.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // a * b + c * d
+    pub fn fmma(a: &Mpfr, b: &Mpfr, c: &Mpfr, d: &Mpfr) -> Mpfr {
+        Mpfr::fmma_round(a, b, c, d, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn fmma_round(a: &Mpfr, b: &Mpfr, c: &Mpfr, d: &Mpfr, rnd: Round) -> Mpfr {
+        Mpfr::fmma_ternary(a, b, c, d, rnd).0
+    }
+
+    pub fn fmma_ternary(a: &Mpfr, b: &Mpfr, c: &Mpfr, d: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let prec = cmp::max(cmp::max(a.get_prec(), b.get_prec()),
+                                cmp::max(c.get_prec(), d.get_prec()));
+            let mut res = Mpfr::new2(prec);
+            let t = mpfr_fmma(&mut res.mpfr, &a.mpfr, &b.mpfr, &c.mpfr, &d.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // a * b - c * d
+    pub fn fmms(a: &Mpfr, b: &Mpfr, c: &Mpfr, d: &Mpfr) -> Mpfr {
+        Mpfr::fmms_round(a, b, c, d, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn fmms_round(a: &Mpfr, b: &Mpfr, c: &Mpfr, d: &Mpfr, rnd: Round) -> Mpfr {
+        Mpfr::fmms_ternary(a, b, c, d, rnd).0
+    }
+
+    pub fn fmms_ternary(a: &Mpfr, b: &Mpfr, c: &Mpfr, d: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let prec = cmp::max(cmp::max(a.get_prec(), b.get_prec()),
+                                cmp::max(c.get_prec(), d.get_prec()));
+            let mut res = Mpfr::new2(prec);
+            let t = mpfr_fmms(&mut res.mpfr, &a.mpfr, &b.mpfr, &c.mpfr, &d.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // sqrt(self^2 + other^2)
+    pub fn hypot(&self, other: &Mpfr) -> Mpfr {
+        self.hypot_round(other, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn hypot_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.hypot_ternary(other, rnd).0
+    }
+
+    pub fn hypot_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_hypot(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // Correctly rounded sum and dot product
+
+    pub fn sum(values: &[&Mpfr], precision: usize, rnd: Round) -> Mpfr {
+        Mpfr::sum_ternary(values, precision, rnd).0
+    }
+
+    pub fn sum_ternary(values: &[&Mpfr], precision: usize, rnd: Round) -> (Mpfr, Ordering) {
+        let ptrs: Vec<mpfr_srcptr> = values.iter().map(|x| &x.mpfr as mpfr_srcptr).collect();
+        unsafe {
+            let mut res = Mpfr::new2(precision);
+            let t = mpfr_sum(&mut res.mpfr, ptrs.as_ptr(), ptrs.len() as c_ulong, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    pub fn dot(a: &[&Mpfr], b: &[&Mpfr], precision: usize, rnd: Round) -> Mpfr {
+        Mpfr::dot_ternary(a, b, precision, rnd).0
+    }
+
+    #[cfg(feature = "mpfr_4_1")]
+    pub fn dot_ternary(a: &[&Mpfr],
+                       b: &[&Mpfr],
+                       precision: usize,
+                       rnd: Round)
+                       -> (Mpfr, Ordering) {
+        assert_eq!(a.len(), b.len());
+        let a_ptrs: Vec<mpfr_srcptr> = a.iter().map(|x| &x.mpfr as mpfr_srcptr).collect();
+        let b_ptrs: Vec<mpfr_srcptr> = b.iter().map(|x| &x.mpfr as mpfr_srcptr).collect();
+        unsafe {
+            let mut res = Mpfr::new2(precision);
+            let t = mpfr_dot(&mut res.mpfr,
+                             a_ptrs.as_ptr(),
+                             b_ptrs.as_ptr(),
+                             a.len() as c_ulong,
+                             rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // Without mpfr_dot, compute each product exactly and round only the sum
+    #[cfg(not(feature = "mpfr_4_1"))]
+    pub fn dot_ternary(a: &[&Mpfr],
+                       b: &[&Mpfr],
+                       precision: usize,
+                       rnd: Round)
+                       -> (Mpfr, Ordering) {
+        assert_eq!(a.len(), b.len());
+        let products: Vec<Mpfr> = a.iter()
+            .zip(b.iter())
+            .map(|(x, y)| unsafe {
+                let mut p = Mpfr::new2(x.get_prec() + y.get_prec());
+                mpfr_mul(&mut p.mpfr, &x.mpfr, &y.mpfr, Round::Nearest);
+                p
+            })
+            .collect();
+        let refs: Vec<&Mpfr> = products.iter().collect();
+        Mpfr::sum_ternary(&refs, precision, rnd)
+    }
+
+    // Mathematical functions
+
+    unary_fn!(sqrt, sqrt_round, sqrt_ternary, mpfr_sqrt);
+    unary_fn!(cbrt, cbrt_round, cbrt_ternary, mpfr_cbrt);
+
+    pub fn root(&self, k: u64) -> Mpfr {
+        self.root_round(k, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn root_round(&self, k: u64, rnd: Round) -> Mpfr {
+        self.root_ternary(k, rnd).0
+    }
+
+    pub fn root_ternary(&self, k: u64, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            let t = mpfr_root(&mut res.mpfr, &self.mpfr, k as c_ulong, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    pub fn pow(&self, other: &Mpfr) -> Mpfr {
+        self.pow_round(other, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn pow_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.pow_ternary(other, rnd).0
+    }
+
+    pub fn pow_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            let t = mpfr_pow(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    unary_fn!(abs, abs_round, abs_ternary, mpfr_abs);
+    unary_fn!(exp, exp_round, exp_ternary, mpfr_exp);
+    unary_fn!(log, log_round, log_ternary, mpfr_log);
+    unary_fn!(log2, log2_round, log2_ternary, mpfr_log2);
+    unary_fn!(log10, log10_round, log10_ternary, mpfr_log10);
+    unary_fn!(log1p, log1p_round, log1p_ternary, mpfr_log1p);
+    unary_fn!(exp2, exp2_round, exp2_ternary, mpfr_exp2);
+    unary_fn!(exp10, exp10_round, exp10_ternary, mpfr_exp10);
+    unary_fn!(expm1, expm1_round, expm1_ternary, mpfr_expm1);
+
+    pub fn log_ui(n: u64, precision: usize) -> Mpfr {
+        Mpfr::log_ui_round(n, precision, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn log_ui_round(n: u64, precision: usize, rnd: Round) -> Mpfr {
+        Mpfr::log_ui_ternary(n, precision, rnd).0
+    }
+
+    pub fn log_ui_ternary(n: u64, precision: usize, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(precision);
+            let t = mpfr_log_ui(&mut res.mpfr, n as c_ulong, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    #[cfg(feature = "mpfr_4_2")]
+    unary_fn!(log2p1, log2p1_round, log2p1_ternary, mpfr_log2p1);
+    #[cfg(feature = "mpfr_4_2")]
+    unary_fn!(log10p1, log10p1_round, log10p1_ternary, mpfr_log10p1);
+    #[cfg(feature = "mpfr_4_2")]
+    unary_fn!(exp2m1, exp2m1_round, exp2m1_ternary, mpfr_exp2m1);
+    #[cfg(feature = "mpfr_4_2")]
+    unary_fn!(exp10m1, exp10m1_round, exp10m1_ternary, mpfr_exp10m1);
+
+    // (1 + self)^n
+    #[cfg(feature = "mpfr_4_2")]
+    pub fn compound(&self, n: i64) -> Mpfr {
+        self.compound_round(n, Round::Nearest)
+    }
+
+    #[cfg(feature = "mpfr_4_2")]
+    #[inline]
+    pub fn compound_round(&self, n: i64, rnd: Round) -> Mpfr {
+        self.compound_ternary(n, rnd).0
+    }
+
+    #[cfg(feature = "mpfr_4_2")]
+    pub fn compound_ternary(&self, n: i64, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            let t = mpfr_compound_si(&mut res.mpfr, &self.mpfr, n as c_long, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    unary_fn!(gamma, gamma_round, gamma_ternary, mpfr_gamma);
+    unary_fn!(lngamma, lngamma_round, lngamma_ternary, mpfr_lngamma);
+
+    // Logarithm of the absolute value of the gamma function, together with
+    // the sign of gamma
+    pub fn lgamma(&self) -> (Mpfr, Sign) {
+        self.lgamma_round(Round::Nearest)
+    }
+
+    #[inline]
+    pub fn lgamma_round(&self, rnd: Round) -> (Mpfr, Sign) {
+        let (res, sign, _) = self.lgamma_ternary(rnd);
+        (res, sign)
+    }
+
+    pub fn lgamma_ternary(&self, rnd: Round) -> (Mpfr, Sign, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            let mut sign: c_int = 1;
+            let t = mpfr_lgamma(&mut res.mpfr, &mut sign, &self.mpfr, rnd);
+            let sign = if sign < 0 { Sign::Negative } else { Sign::Positive };
+            (res, sign, ternary(t))
+        }
+    }
+
+    unary_fn!(digamma, digamma_round, digamma_ternary, mpfr_digamma);
+
+    pub fn beta(&self, other: &Mpfr) -> Mpfr {
+        self.beta_round(other, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn beta_round(&self, other: &Mpfr, rnd: Round) -> Mpfr {
+        self.beta_ternary(other, rnd).0
+    }
+
+    pub fn beta_ternary(&self, other: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
+            let t = mpfr_beta(&mut res.mpfr, &self.mpfr, &other.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    unary_fn!(erf, erf_round, erf_ternary, mpfr_erf);
+    unary_fn!(erfc, erfc_round, erfc_ternary, mpfr_erfc);
+    unary_fn!(zeta, zeta_round, zeta_ternary, mpfr_zeta);
+
+    pub fn zeta_ui(n: u64, precision: usize) -> Mpfr {
+        Mpfr::zeta_ui_round(n, precision, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn zeta_ui_round(n: u64, precision: usize, rnd: Round) -> Mpfr {
+        Mpfr::zeta_ui_ternary(n, precision, rnd).0
+    }
+
+    pub fn zeta_ui_ternary(n: u64, precision: usize, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(precision);
+            let t = mpfr_zeta_ui(&mut res.mpfr, n as c_ulong, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    unary_fn!(eint, eint_round, eint_ternary, mpfr_eint);
+    unary_fn!(li2, li2_round, li2_ternary, mpfr_li2);
+    unary_fn!(ai, ai_round, ai_ternary, mpfr_ai);
+
+    // Bessel functions
+
+    unary_fn!(j0, j0_round, j0_ternary, mpfr_j0);
+    unary_fn!(j1, j1_round, j1_ternary, mpfr_j1);
+
+    pub fn jn(&self, n: i64) -> Mpfr {
+        self.jn_round(n, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn jn_round(&self, n: i64, rnd: Round) -> Mpfr {
+        self.jn_ternary(n, rnd).0
+    }
+
+    pub fn jn_ternary(&self, n: i64, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            let t = mpfr_jn(&mut res.mpfr, n as c_long, &self.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    unary_fn!(y0, y0_round, y0_ternary, mpfr_y0);
+    unary_fn!(y1, y1_round, y1_ternary, mpfr_y1);
+
+    pub fn yn(&self, n: i64) -> Mpfr {
+        self.yn_round(n, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn yn_round(&self, n: i64, rnd: Round) -> Mpfr {
+        self.yn_ternary(n, rnd).0
+    }
+
+    pub fn yn_ternary(&self, n: i64, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            let t = mpfr_yn(&mut res.mpfr, n as c_long, &self.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // Trigonometric functions
+
+    unary_fn!(sin, sin_round, sin_ternary, mpfr_sin);
+    unary_fn!(cos, cos_round, cos_ternary, mpfr_cos);
+    unary_fn!(tan, tan_round, tan_ternary, mpfr_tan);
+    unary_fn!(sec, sec_round, sec_ternary, mpfr_sec);
+    unary_fn!(csc, csc_round, csc_ternary, mpfr_csc);
+    unary_fn!(cot, cot_round, cot_ternary, mpfr_cot);
+
+    pub fn sin_cos(&self) -> (Mpfr, Mpfr) {
+        self.sin_cos_round(Round::Nearest)
+    }
+
+    pub fn sin_cos_round(&self, rnd: Round) -> (Mpfr, Mpfr) {
+        let ((sin, _), (cos, _)) = self.sin_cos_ternary(rnd);
+        (sin, cos)
+    }
+
+    pub fn sin_cos_ternary(&self, rnd: Round) -> ((Mpfr, Ordering), (Mpfr, Ordering)) {
+        unsafe {
+            let mut sin = Mpfr::new2(self.get_prec());
+            let mut cos = Mpfr::new2(self.get_prec());
+            let t = mpfr_sin_cos(&mut sin.mpfr, &mut cos.mpfr, &self.mpfr, rnd);
+            let (t_sin, t_cos) = ternary_pair(t);
+            ((sin, t_sin), (cos, t_cos))
+        }
+    }
+
+    unary_fn!(asin, asin_round, asin_ternary, mpfr_asin);
+    unary_fn!(acos, acos_round, acos_ternary, mpfr_acos);
+    unary_fn!(atan, atan_round, atan_ternary, mpfr_atan);
+
+    // Angle of the point (x, self), in [-pi, pi]. For a zero self and a
+    // negative x, the sign of the zero picks pi or -pi.
+    pub fn atan2(&self, x: &Mpfr) -> Mpfr {
+        self.atan2_round(x, Round::Nearest)
+    }
+
+    #[inline]
+    pub fn atan2_round(&self, x: &Mpfr, rnd: Round) -> Mpfr {
+        self.atan2_ternary(x, rnd).0
+    }
+
+    pub fn atan2_ternary(&self, x: &Mpfr, rnd: Round) -> (Mpfr, Ordering) {
+        unsafe {
+            let mut res = Mpfr::new2(cmp::max(self.get_prec(), x.get_prec()));
+            let t = mpfr_atan2(&mut res.mpfr, &self.mpfr, &x.mpfr, rnd);
+            (res, ternary(t))
+        }
+    }
+
+    // Hyperbolic functions
+
+    unary_fn!(sinh, sinh_round, sinh_ternary, mpfr_sinh);
+    unary_fn!(cosh, cosh_round, cosh_ternary, mpfr_cosh);
+    unary_fn!(tanh, tanh_round, tanh_ternary, mpfr_tanh);
+    unary_fn!(sech, sech_round, sech_ternary, mpfr_sech);
+    unary_fn!(csch, csch_round, csch_ternary, mpfr_csch);
+    unary_fn!(coth, coth_round, coth_ternary, mpfr_coth);
+
+    pub fn sinh_cosh(&self) -> (Mpfr, Mpfr) {
+        self.sinh_cosh_round(Round::Nearest)
+    }
+
+    pub fn sinh_cosh_round(&self, rnd: Round) -> (Mpfr, Mpfr) {
+        let ((sinh, _), (cosh, _)) = self.sinh_cosh_ternary(rnd);
+        (sinh, cosh)
+    }
+
+    pub fn sinh_cosh_ternary(&self, rnd: Round) -> ((Mpfr, Ordering), (Mpfr, Ordering)) {
+        unsafe {
+            let mut sinh = Mpfr::new2(self.get_prec());
+            let mut cosh = Mpfr::new2(self.get_prec());
+            let t = mpfr_sinh_cosh(&mut sinh.mpfr, &mut cosh.mpfr, &self.mpfr, rnd);
+            let (t_sinh, t_cosh) = ternary_pair(t);
+            ((sinh, t_sinh), (cosh, t_cosh))
+        }
+    }
+
+    unary_fn!(asinh, asinh_round, asinh_ternary, mpfr_asinh);
+    unary_fn!(acosh, acosh_round, acosh_ternary, mpfr_acosh);
+    unary_fn!(atanh, atanh_round, atanh_ternary, mpfr_atanh);
+
+    // Conversions
+
+    pub fn to_i64_round(&self, rnd: Round) -> i64 {
+        unsafe { mpfr_get_si(&self.mpfr, rnd) as i64 }
+    }
+
+    pub fn to_u64_round(&self, rnd: Round) -> u64 {
+        unsafe { mpfr_get_ui(&self.mpfr, rnd) as u64 }
+    }
+
+    // Like to_i64_round and to_u64_round, these saturate and give 0 for NaN
+    pub fn to_i128_round(&self, rnd: Round) -> i128 {
+        match self.to_int_checked(rnd) {
+            Ok(x) => x,
+            Err(TryFromMpfrError::Overflow) if self.is_sign_negative() => i128::MIN,
+            Err(TryFromMpfrError::Overflow) => i128::MAX,
+            Err(_) => 0,
+        }
+    }
+
+    pub fn to_u128_round(&self, rnd: Round) -> u128 {
+        match self.to_int_checked(rnd) {
+            Ok(x) => x,
+            Err(TryFromMpfrError::Overflow) if !self.is_sign_negative() => u128::MAX,
+            Err(_) => 0,
+        }
+    }
+
+    pub fn to_f64_round(&self, rnd: Round) -> f64 {
+        unsafe { mpfr_get_d(&self.mpfr, rnd) as f64 }
+    }
+
+    pub fn to_f32_round(&self, rnd: Round) -> f32 {
+        unsafe { mpfr_get_flt(&self.mpfr, rnd) as f32 }
+    }
+
+    pub fn to_mpz_round(&self, rnd: Round) -> Mpz {
+        unsafe {
+            let mut result = Mpz::new();
+            mpfr_get_z(result.inner_mut(), &self.mpfr, rnd);
+            result
+        }
+    }
+
+    // Exact conversions, None for NaN and infinities
+
+    pub fn to_mpq(&self) -> Option<Mpq> {
+        if !self.is_finite() {
+            return None;
+        }
+        unsafe {
+            let mut result = Mpq::new();
+            mpfr_get_q(result.inner_mut(), &self.mpfr);
+            Some(result)
+        }
+    }
+
+    // (m, e) such that self == m * 2^e
+    pub fn to_integer_exp(&self) -> Option<(Mpz, i64)> {
+        if !self.is_finite() {
+            return None;
+        }
+        if self.is_zero() {
+            return Some((Mpz::new(), 0));
+        }
+        unsafe {
+            let mut result = Mpz::new();
+            let exp = mpfr_get_z_2exp(result.inner_mut(), &self.mpfr);
+            Some((result, exp as i64))
+        }
+    }
+
+    pub fn to_mpf_round(&self, rnd: Round) -> Mpf {
+        unsafe {
+            let mut result = Mpf::new(self.get_prec());
+            mpfr_get_f(result.inner_mut(), &self.mpfr, rnd);
+            result
+        }
+    }
+}
+
+// Comparison
+//
+// PartialEq and PartialOrd follow IEEE 754: NaN is unordered with respect to
+// everything, including itself, and -0 == +0. Use total_cmp for sorting.
+
+impl PartialEq for Mpfr {
+    fn eq(&self, other: &Mpfr) -> bool {
+        unsafe { mpfr_equal_p(&self.mpfr, &other.mpfr) != 0 }
+    }
+}
+
+impl PartialOrd for Mpfr {
+    fn partial_cmp(&self, other: &Mpfr) -> Option<Ordering> {
+        unsafe {
+            if mpfr_unordered_p(&self.mpfr, &other.mpfr) != 0 {
+                None
+            } else {
+                Some(mpfr_cmp(&self.mpfr, &other.mpfr).cmp(&0))
+            }
+        }
+    }
+}
+
+impl Mpfr {
+    // Total order of IEEE 754 (totalOrder): -NaN < -Inf < ... < -0 < +0 <
+    // ... < +Inf < +NaN
+    pub fn total_cmp(&self, other: &Mpfr) -> Ordering {
+        unsafe {
+            let self_neg = mpfr_signbit(&self.mpfr) != 0;
+            let other_neg = mpfr_signbit(&other.mpfr) != 0;
+            if self_neg != other_neg {
+                return if self_neg { Ordering::Less } else { Ordering::Greater };
+            }
+
+            // Same sign: compare magnitudes, with NaN above infinity
+            let magnitude = match (mpfr_nan_p(&self.mpfr) != 0, mpfr_nan_p(&other.mpfr) != 0) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => mpfr_cmpabs(&self.mpfr, &other.mpfr).cmp(&0),
+            };
+            if self_neg { magnitude.reverse() } else { magnitude }
+        }
+    }
+}
+
+// Conversions
+
+impl From<i64> for Mpfr {
+    fn from(x: i64) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_i64_round(x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<u64> for Mpfr {
+    fn from(x: u64) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_u64_round(x, Round::Nearest);
+        mpfr
+    }
+}
+
+// At least 128 bits of precision, so that the conversion is exact
+impl From<i128> for Mpfr {
+    fn from(x: i128) -> Mpfr {
+        let mut mpfr = Mpfr::new2(cmp::max(Mpfr::get_default_prec(), 128));
+        mpfr.set_i128_round(x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<u128> for Mpfr {
+    fn from(x: u128) -> Mpfr {
+        let mut mpfr = Mpfr::new2(cmp::max(Mpfr::get_default_prec(), 128));
+        mpfr.set_u128_round(x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<i32> for Mpfr {
+    fn from(x: i32) -> Mpfr {
+        From::<i64>::from(x as i64)
+    }
+}
+
+impl From<u32> for Mpfr {
+    fn from(x: u32) -> Mpfr {
+        From::<u64>::from(x as u64)
+    }
+}
+
+impl From<i16> for Mpfr {
+    fn from(x: i16) -> Mpfr {
+        From::<i64>::from(x as i64)
+    }
+}
+
+impl From<u16> for Mpfr {
+    fn from(x: u16) -> Mpfr {
+        From::<u64>::from(x as u64)
+    }
+}
+
+impl From<i8> for Mpfr {
+    fn from(x: i8) -> Mpfr {
+        From::<i64>::from(x as i64)
+    }
+}
+
+impl From<u8> for Mpfr {
+    fn from(x: u8) -> Mpfr {
+        From::<u64>::from(x as u64)
+    }
+}
+
+impl From<f64> for Mpfr {
+    fn from(x: f64) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_f64_round(x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<f32> for Mpfr {
+    fn from(x: f32) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_f32_round(x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<Mpz> for Mpfr {
+    fn from(x: Mpz) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_mpz_round(&x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<Mpq> for Mpfr {
+    fn from(x: Mpq) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_mpq_round(&x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl From<Mpf> for Mpfr {
+    fn from(x: Mpf) -> Mpfr {
+        let mut mpfr = Mpfr::new();
+        mpfr.set_mpf_round(&x, Round::Nearest);
+        mpfr
+    }
+}
+
+impl<'a> Into<f64> for &'a Mpfr {
+    fn into(self) -> f64 {
+        self.to_f64_round(Round::Nearest)
+    }
+}
+
+impl<'a> Into<f32> for &'a Mpfr {
+    fn into(self) -> f32 {
+        self.to_f32_round(Round::Nearest)
+    }
+}
+
+impl<'a> Into<Mpz> for &'a Mpfr {
+    fn into(self) -> Mpz {
+        self.to_mpz_round(Round::Nearest)
+    }
+}
+
+impl<'a> Into<Mpf> for &'a Mpfr {
+    fn into(self) -> Mpf {
+        self.to_mpf_round(Round::Nearest)
+    }
+}
+
+// Checked integer conversions
+//
+// TryFrom only accepts values that are exactly an integer in the range of the
+// target type; use to_int_checked to round first. The to_*_round methods
+// saturate instead, and give 0 for NaN.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TryFromMpfrError {
+    Nan,
+    Overflow, // out of range, including infinities
+    NotInteger,
+}
+
+impl fmt::Display for TryFromMpfrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            TryFromMpfrError::Nan => "cannot convert NaN to an integer",
+            TryFromMpfrError::Overflow => "value out of range of the integer type",
+            TryFromMpfrError::NotInteger => "value is not an integer",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for TryFromMpfrError {}
+
+impl Mpfr {
+    // Rounds self to an integer with `rnd`, then converts it with TryFrom
+    pub fn to_int_checked<T>(&self, rnd: Round) -> Result<T, TryFromMpfrError>
+        where T: for<'a> TryFrom<&'a Mpfr, Error = TryFromMpfrError>
+    {
+        T::try_from(&self.rint(self.get_prec(), rnd))
+    }
+
+    fn check_integer(&self) -> Result<(), TryFromMpfrError> {
+        if self.is_nan() {
+            Err(TryFromMpfrError::Nan)
+        } else if self.is_infinite() {
+            Err(TryFromMpfrError::Overflow)
+        } else if !self.is_integer() {
+            Err(TryFromMpfrError::NotInteger)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Splits an integer into floor(self / 2^64) and the low 64 bits
+    fn split_u64(&self) -> (Mpfr, u64) {
+        let hi = (self >> 64).floor();
+        unsafe {
+            let mut lo = Mpfr::new2(64);
+            mpfr_sub(&mut lo.mpfr, &self.mpfr, &(&hi << 64).mpfr, Round::Zero);
+            (hi, mpfr_get_ui(&lo.mpfr, Round::Zero) as u64)
+        }
+    }
+}
+
+// MPFR has no fits functions for 8-bit types. Like the ones below, these are
+// only called on integers, so the rounding mode does not matter.
+unsafe fn fits_i8(op: mpfr_srcptr, _rnd: Round) -> c_int {
+    (mpfr_cmp_si(op, i8::MIN as c_long) >= 0 && mpfr_cmp_si(op, i8::MAX as c_long) <= 0) as c_int
+}
+
+unsafe fn fits_u8(op: mpfr_srcptr, _rnd: Round) -> c_int {
+    (mpfr_cmp_si(op, 0) >= 0 && mpfr_cmp_ui(op, u8::MAX as c_ulong) <= 0) as c_int
+}
+
+try_from_fits!(i8, fits_i8, mpfr_get_si);
+try_from_fits!(u8, fits_u8, mpfr_get_ui);
+try_from_fits!(i16, mpfr_fits_sshort_p, mpfr_get_si);
+try_from_fits!(u16, mpfr_fits_ushort_p, mpfr_get_ui);
+try_from_fits!(i32, mpfr_fits_sint_p, mpfr_get_si);
+try_from_fits!(u32, mpfr_fits_uint_p, mpfr_get_ui);
+try_from_fits!(i64, mpfr_fits_slong_p, mpfr_get_si);
+try_from_fits!(u64, mpfr_fits_ulong_p, mpfr_get_ui);
+
+// There is no 128-bit support in MPFR either, so these convert the two
+// 64-bit halves separately
+
+impl<'a> TryFrom<&'a Mpfr> for i128 {
+    type Error = TryFromMpfrError;
+    fn try_from(x: &'a Mpfr) -> Result<i128, TryFromMpfrError> {
+        x.check_integer()?;
+        unsafe {
+            // -2^127 <= x < 2^127
+            if mpfr_cmp_si_2exp(&x.mpfr, -1, 127) < 0 || mpfr_cmp_ui_2exp(&x.mpfr, 1, 127) >= 0 {
+                return Err(TryFromMpfrError::Overflow);
+            }
+            let (hi, lo) = x.split_u64();
+            Ok(((mpfr_get_si(&hi.mpfr, Round::Zero) as i128) << 64) | lo as i128)
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a Mpfr> for u128 {
+    type Error = TryFromMpfrError;
+    fn try_from(x: &'a Mpfr) -> Result<u128, TryFromMpfrError> {
+        x.check_integer()?;
+        unsafe {
+            // 0 <= x < 2^128
+            if mpfr_cmp_si(&x.mpfr, 0) < 0 || mpfr_cmp_ui_2exp(&x.mpfr, 1, 128) >= 0 {
+                return Err(TryFromMpfrError::Overflow);
+            }
+            let (hi, lo) = x.split_u64();
+            Ok(((mpfr_get_ui(&hi.mpfr, Round::Zero) as u128) << 64) | lo as u128)
+        }
+    }
+}
+
+// Every finite value is a rational, so this only fails on NaN and infinities
+impl<'a> TryFrom<&'a Mpfr> for Mpq {
+    type Error = TryFromMpfrError;
+    fn try_from(x: &'a Mpfr) -> Result<Mpq, TryFromMpfrError> {
+        if x.is_nan() {
+            return Err(TryFromMpfrError::Nan);
+        }
+        x.to_mpq().ok_or(TryFromMpfrError::Overflow)
+    }
+}
+
+//
+// Addition
+//
 // Supports:
 // Mpfr + Mpfr
 // Mpfr + f64, f64 + Mpfr
@@ -553,15 +1840,9 @@ impl<'a> Into<Mpf> for &'a Mpfr {
 
 impl<'a, 'b> Add<&'a Mpfr> for &'b Mpfr {
     type Output = Mpfr;
+    #[inline]
     fn add(self, other: &Mpfr) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
-            mpfr_add(&mut res.mpfr,
-                     &self.mpfr,
-                     &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+        self.add_round(other, Round::Nearest)
     }
 }
 
@@ -576,7 +1857,7 @@ impl<'a> Add<&'a Mpfr> for Mpfr {
             mpfr_add(&mut self.mpfr,
                      &self.mpfr,
                      &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
+                     Round::Nearest);
             self
         }
     }
@@ -590,7 +1871,7 @@ impl Add<Mpfr> for f64 {
             mpfr_add_d(&mut res.mpfr,
                        &other.mpfr,
                        self as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -604,7 +1885,7 @@ impl<'a> Add<&'a Mpfr> for f64 {
             mpfr_add_d(&mut res.mpfr,
                        &other.mpfr,
                        self as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -618,7 +1899,7 @@ impl Add<f64> for Mpfr {
             mpfr_add_d(&mut self.mpfr,
                        &self.mpfr,
                        other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             self
         }
     }
@@ -632,7 +1913,7 @@ impl<'a> Add<f64> for &'a Mpfr {
             mpfr_add_d(&mut res.mpfr,
                        &self.mpfr,
                        other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -646,7 +1927,7 @@ impl Add<Mpfr> for i64 {
             mpfr_add_si(&mut res.mpfr,
                         &other.mpfr,
                         self as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -660,7 +1941,7 @@ impl<'a> Add<&'a Mpfr> for i64 {
             mpfr_add_si(&mut res.mpfr,
                         &other.mpfr,
                         self as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -674,7 +1955,7 @@ impl Add<i64> for Mpfr {
             mpfr_add_si(&mut self.mpfr,
                         &self.mpfr,
                         other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             self
         }
     }
@@ -688,7 +1969,7 @@ impl<'a> Add<i64> for &'a Mpfr {
             mpfr_add_si(&mut res.mpfr,
                         &self.mpfr,
                         other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -706,15 +1987,9 @@ impl<'a> Add<i64> for &'a Mpfr {
 
 impl<'a, 'b> Sub<&'a Mpfr> for &'b Mpfr {
     type Output = Mpfr;
+    #[inline]
     fn sub(self, other: &Mpfr) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
-            mpfr_sub(&mut res.mpfr,
-                     &self.mpfr,
-                     &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+        self.sub_round(other, Round::Nearest)
     }
 }
 
@@ -729,7 +2004,7 @@ impl<'a> Sub<&'a Mpfr> for Mpfr {
             mpfr_sub(&mut self.mpfr,
                      &self.mpfr,
                      &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
+                     Round::Nearest);
             self
         }
     }
@@ -743,7 +2018,7 @@ impl Sub<Mpfr> for f64 {
             mpfr_d_sub(&mut res.mpfr,
                        self as c_double,
                        &other.mpfr,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -757,7 +2032,7 @@ impl<'a> Sub<&'a Mpfr> for f64 {
             mpfr_d_sub(&mut res.mpfr,
                        self as c_double,
                        &other.mpfr,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -771,7 +2046,7 @@ impl Sub<f64> for Mpfr {
             mpfr_sub_d(&mut self.mpfr,
                        &self.mpfr,
                        other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             self
         }
     }
@@ -785,7 +2060,7 @@ impl<'a> Sub<f64> for &'a Mpfr {
             mpfr_sub_d(&mut res.mpfr,
                        &self.mpfr,
                        other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -799,7 +2074,7 @@ impl Sub<Mpfr> for i64 {
             mpfr_si_sub(&mut res.mpfr,
                         self as c_long,
                         &other.mpfr,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -813,7 +2088,7 @@ impl<'a> Sub<&'a Mpfr> for i64 {
             mpfr_si_sub(&mut res.mpfr,
                         self as c_long,
                         &other.mpfr,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -827,7 +2102,7 @@ impl Sub<i64> for Mpfr {
             mpfr_sub_si(&mut self.mpfr,
                         &self.mpfr,
                         other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             self
         }
     }
@@ -841,7 +2116,7 @@ impl<'a> Sub<i64> for &'a Mpfr {
             mpfr_sub_si(&mut res.mpfr,
                         &self.mpfr,
                         other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -859,15 +2134,9 @@ impl<'a> Sub<i64> for &'a Mpfr {
 
 impl<'a, 'b> Mul<&'a Mpfr> for &'b Mpfr {
     type Output = Mpfr;
+    #[inline]
     fn mul(self, other: &Mpfr) -> Mpfr {
-        unsafe {
-            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
-            mpfr_mul(&mut res.mpfr,
-                     &self.mpfr,
-                     &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+        self.mul_round(other, Round::Nearest)
     }
 }
 
@@ -883,7 +2152,7 @@ impl<'a> Mul<&'a Mpfr> for Mpfr {
             mpfr_mul(&mut self.mpfr,
                      &self.mpfr,
                      &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
+                     Round::Nearest);
             self
         }
     }
@@ -897,7 +2166,7 @@ impl Mul<Mpfr> for f64 {
             mpfr_mul_d(&mut res.mpfr,
                        &other.mpfr,
                        self as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -911,7 +2180,7 @@ impl<'a> Mul<&'a Mpfr> for f64 {
             mpfr_mul_d(&mut res.mpfr,
                        &other.mpfr,
                        self as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -925,7 +2194,7 @@ impl Mul<f64> for Mpfr {
             mpfr_mul_d(&mut self.mpfr,
                        &self.mpfr,
                        other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             self
         }
     }
@@ -939,7 +2208,7 @@ impl<'a> Mul<f64> for &'a Mpfr {
             mpfr_mul_d(&mut res.mpfr,
                        &self.mpfr,
                        other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+                       Round::Nearest);
             res
         }
     }
@@ -953,7 +2222,7 @@ impl Mul<Mpfr> for i64 {
             mpfr_mul_si(&mut res.mpfr,
                         &other.mpfr,
                         self as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -967,7 +2236,7 @@ impl<'a> Mul<&'a Mpfr> for i64 {
             mpfr_mul_si(&mut res.mpfr,
                         &other.mpfr,
                         self as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -981,7 +2250,7 @@ impl Mul<i64> for Mpfr {
             mpfr_mul_si(&mut self.mpfr,
                         &self.mpfr,
                         other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             self
         }
     }
@@ -995,7 +2264,7 @@ impl<'a> Mul<i64> for &'a Mpfr {
             mpfr_mul_si(&mut res.mpfr,
                         &self.mpfr,
                         other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+                        Round::Nearest);
             res
         }
     }
@@ -1013,186 +2282,353 @@ impl<'a> Mul<i64> for &'a Mpfr {
 
 impl<'a, 'b> Div<&'a Mpfr> for &'b Mpfr {
     type Output = Mpfr;
+    #[inline]
     fn div(self, other: &Mpfr) -> Mpfr {
+        self.div_round(other, Round::Nearest)
+    }
+}
+
+impl<'a> Div<&'a Mpfr> for Mpfr {
+    type Output = Mpfr;
+    #[inline]
+    fn div(mut self, other: &Mpfr) -> Mpfr {
+        if other.get_prec() > self.get_prec() {
+            return &self / other;
+        }
         unsafe {
-            if mpfr_cmp_ui(&other.mpfr, 0) == 0 {
+            if other.is_zero() {
                 panic!("divide by zero")
             }
-
-            let mut res = Mpfr::new2(cmp::max(self.get_prec(), other.get_prec()));
-            mpfr_div(&mut res.mpfr,
+            mpfr_div(&mut self.mpfr,
                      &self.mpfr,
                      &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
+                     Round::Nearest);
+            self
+        }
+    }
+}
+
+impl Div<Mpfr> for f64 {
+	type Output = Mpfr;
+    fn div(self, other: Mpfr) -> Mpfr {
+        unsafe {
+            if other.is_zero() {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(other.get_prec());
+            mpfr_d_div(&mut res.mpfr,
+                       self as c_double,
+                       &other.mpfr,
+                       Round::Nearest);
+            res
+        }
+    }
+}
+
+impl<'a> Div<&'a Mpfr> for f64 {
+	type Output = Mpfr;
+    fn div(self, other: &'a Mpfr) -> Mpfr {
+        unsafe {
+            if other.is_zero() {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(other.get_prec());
+            mpfr_d_div(&mut res.mpfr,
+                       self as c_double,
+                       &other.mpfr,
+                       Round::Nearest);
+            res
+        }
+    }
+}
+
+impl Div<f64> for Mpfr {
+	type Output = Mpfr;
+    #[inline]
+    fn div(mut self, other: f64) -> Mpfr {
+        unsafe {
+            if other == 0.0 {
+                panic!("divide by zero")
+            }
+
+            mpfr_div_d(&mut self.mpfr,
+                       &self.mpfr,
+                       other as c_double,
+                       Round::Nearest);
+            self
+        }
+    }
+}
+
+impl<'a> Div<f64> for &'a Mpfr {
+	type Output = Mpfr;
+    fn div(self, other: f64) -> Mpfr {
+        unsafe {
+            if other == 0.0 {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(self.get_prec());
+            mpfr_div_d(&mut res.mpfr,
+                       &self.mpfr,
+                       other as c_double,
+                       Round::Nearest);
+            res
+        }
+    }
+}
+
+impl Div<Mpfr> for i64 {
+	type Output = Mpfr;
+    fn div(self, other: Mpfr) -> Mpfr {
+        unsafe {
+            if other.is_zero() {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(other.get_prec());
+            mpfr_si_div(&mut res.mpfr,
+                        self as c_long,
+                        &other.mpfr,
+                        Round::Nearest);
+            res
+        }
+    }
+}
+
+impl<'a> Div<&'a Mpfr> for i64 {
+	type Output = Mpfr;
+    fn div(self, other: &'a Mpfr) -> Mpfr {
+        unsafe {
+            if other.is_zero() {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(other.get_prec());
+            mpfr_si_div(&mut res.mpfr,
+                        self as c_long,
+                        &other.mpfr,
+                        Round::Nearest);
+            res
+        }
+    }
+}
+
+impl Div<i64> for Mpfr {
+	type Output = Mpfr;
+    #[inline]
+    fn div(mut self, other: i64) -> Mpfr {
+        unsafe {
+            if other == 0 {
+                panic!("divide by zero")
+            }
+
+            mpfr_div_si(&mut self.mpfr,
+                        &self.mpfr,
+                        other as c_long,
+                        Round::Nearest);
+            self
+        }
+    }
+}
+
+impl<'a> Div<i64> for &'a Mpfr {
+	type Output = Mpfr;
+    fn div(self, other: i64) -> Mpfr {
+        unsafe {
+            if other == 0 {
+                panic!("divide by zero")
+            }
+
+            let mut res = Mpfr::new2(self.get_prec());
+            mpfr_div_si(&mut res.mpfr,
+                        &self.mpfr,
+                        other as c_long,
+                        Round::Nearest);
             res
         }
     }
 }
 
-impl<'a> Div<&'a Mpfr> for Mpfr {
+//
+// Remainder
+//
+// Supports:
+// Mpfr % Mpfr
+// Mpfr % f64, f64 % Mpfr
+// Mpfr % i64, i64 % Mpfr
+//
+// There is no mpfr_fmod_d or mpfr_fmod_si, so scalar operands are converted
+// to an Mpfr (exactly) first
+//
+
+impl<'a, 'b> Rem<&'a Mpfr> for &'b Mpfr {
+    type Output = Mpfr;
+    #[inline]
+    fn rem(self, other: &Mpfr) -> Mpfr {
+        self.rem_round(other, Round::Nearest)
+    }
+}
+
+impl<'a> Rem<&'a Mpfr> for Mpfr {
     type Output = Mpfr;
     #[inline]
-    fn div(mut self, other: &Mpfr) -> Mpfr {
+    fn rem(mut self, other: &Mpfr) -> Mpfr {
         if other.get_prec() > self.get_prec() {
-            return &self / other;
+            return &self % other;
         }
         unsafe {
-            if mpfr_cmp_ui(&other.mpfr, 0) == 0 {
+            if other.is_zero() {
                 panic!("divide by zero")
             }
-            mpfr_div(&mut self.mpfr,
-                     &self.mpfr,
-                     &other.mpfr,
-                     mpfr_rnd_t::MPFR_RNDN);
+            mpfr_fmod(&mut self.mpfr,
+                      &self.mpfr,
+                      &other.mpfr,
+                      Round::Nearest);
             self
         }
     }
 }
 
-impl Div<Mpfr> for f64 {
+impl Rem<Mpfr> for f64 {
 	type Output = Mpfr;
-    fn div(self, other: Mpfr) -> Mpfr {
-        unsafe {
-            if mpfr_cmp_ui(&other.mpfr, 0) == 0 {
-                panic!("divide by zero")
-            }
-
-            let mut res = Mpfr::new2(other.get_prec());
-            mpfr_d_div(&mut res.mpfr,
-                       self as c_double,
-                       &other.mpfr,
-                       mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+    fn rem(self, other: Mpfr) -> Mpfr {
+        self % &other
     }
 }
 
-impl<'a> Div<&'a Mpfr> for f64 {
+impl<'a> Rem<&'a Mpfr> for f64 {
 	type Output = Mpfr;
-    fn div(self, other: &'a Mpfr) -> Mpfr {
+    fn rem(self, other: &'a Mpfr) -> Mpfr {
         unsafe {
-            if mpfr_cmp_ui(&other.mpfr, 0) == 0 {
+            if other.is_zero() {
                 panic!("divide by zero")
             }
 
+            let (x, _) = Mpfr::from_f64_ternary(53, self, Round::Nearest);
             let mut res = Mpfr::new2(other.get_prec());
-            mpfr_d_div(&mut res.mpfr,
-                       self as c_double,
-                       &other.mpfr,
-                       mpfr_rnd_t::MPFR_RNDN);
+            mpfr_fmod(&mut res.mpfr, &x.mpfr, &other.mpfr, Round::Nearest);
             res
         }
     }
 }
 
-impl Div<f64> for Mpfr {
+impl Rem<f64> for Mpfr {
 	type Output = Mpfr;
     #[inline]
-    fn div(mut self, other: f64) -> Mpfr {
-        unsafe {
-            if other == 0.0 {
-                panic!("divide by zero")
-            }
-
-            mpfr_div_d(&mut self.mpfr,
-                       &self.mpfr,
-                       other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
-            self
-        }
+    fn rem(self, other: f64) -> Mpfr {
+        &self % other
     }
 }
 
-impl<'a> Div<f64> for &'a Mpfr {
+impl<'a> Rem<f64> for &'a Mpfr {
 	type Output = Mpfr;
-    fn div(self, other: f64) -> Mpfr {
+    fn rem(self, other: f64) -> Mpfr {
         unsafe {
             if other == 0.0 {
                 panic!("divide by zero")
             }
 
+            let (y, _) = Mpfr::from_f64_ternary(53, other, Round::Nearest);
             let mut res = Mpfr::new2(self.get_prec());
-            mpfr_div_d(&mut res.mpfr,
-                       &self.mpfr,
-                       other as c_double,
-                       mpfr_rnd_t::MPFR_RNDN);
+            mpfr_fmod(&mut res.mpfr, &self.mpfr, &y.mpfr, Round::Nearest);
             res
         }
     }
 }
 
-impl Div<Mpfr> for i64 {
+impl Rem<Mpfr> for i64 {
 	type Output = Mpfr;
-    fn div(self, other: Mpfr) -> Mpfr {
-        unsafe {
-            if mpfr_cmp_ui(&other.mpfr, 0) == 0 {
-                panic!("divide by zero")
-            }
-
-            let mut res = Mpfr::new2(other.get_prec());
-            mpfr_si_div(&mut res.mpfr,
-                        self as c_long,
-                        &other.mpfr,
-                        mpfr_rnd_t::MPFR_RNDN);
-            res
-        }
+    fn rem(self, other: Mpfr) -> Mpfr {
+        self % &other
     }
 }
 
-impl<'a> Div<&'a Mpfr> for i64 {
+impl<'a> Rem<&'a Mpfr> for i64 {
 	type Output = Mpfr;
-    fn div(self, other: &'a Mpfr) -> Mpfr {
+    fn rem(self, other: &'a Mpfr) -> Mpfr {
         unsafe {
-            if mpfr_cmp_ui(&other.mpfr, 0) == 0 {
+            if other.is_zero() {
                 panic!("divide by zero")
             }
 
+            let (x, _) = Mpfr::from_i64_ternary(64, self, Round::Nearest);
             let mut res = Mpfr::new2(other.get_prec());
-            mpfr_si_div(&mut res.mpfr,
-                        self as c_long,
-                        &other.mpfr,
-                        mpfr_rnd_t::MPFR_RNDN);
+            mpfr_fmod(&mut res.mpfr, &x.mpfr, &other.mpfr, Round::Nearest);
             res
         }
     }
 }
 
-impl Div<i64> for Mpfr {
+impl Rem<i64> for Mpfr {
 	type Output = Mpfr;
     #[inline]
-    fn div(mut self, other: i64) -> Mpfr {
-        unsafe {
-            if other == 0 {
-                panic!("divide by zero")
-            }
-
-            mpfr_div_si(&mut self.mpfr,
-                        &self.mpfr,
-                        other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
-            self
-        }
+    fn rem(self, other: i64) -> Mpfr {
+        &self % other
     }
 }
 
-impl<'a> Div<i64> for &'a Mpfr {
+impl<'a> Rem<i64> for &'a Mpfr {
 	type Output = Mpfr;
-    fn div(self, other: i64) -> Mpfr {
+    fn rem(self, other: i64) -> Mpfr {
         unsafe {
             if other == 0 {
                 panic!("divide by zero")
             }
 
+            let (y, _) = Mpfr::from_i64_ternary(64, other, Round::Nearest);
             let mut res = Mpfr::new2(self.get_prec());
-            mpfr_div_si(&mut res.mpfr,
-                        &self.mpfr,
-                        other as c_long,
-                        mpfr_rnd_t::MPFR_RNDN);
+            mpfr_fmod(&mut res.mpfr, &self.mpfr, &y.mpfr, Round::Nearest);
             res
         }
     }
 }
 
+// Summation and product over iterators
+//
+// Both work at the largest precision among the terms (the default precision
+// if there are none). Sums are rounded once; products are rounded after every
+// factor.
+
+impl Sum for Mpfr {
+    fn sum<I: Iterator<Item = Mpfr>>(iter: I) -> Mpfr {
+        let values: Vec<Mpfr> = iter.collect();
+        values.iter().sum()
+    }
+}
+
+impl<'a> Sum<&'a Mpfr> for Mpfr {
+    fn sum<I: Iterator<Item = &'a Mpfr>>(iter: I) -> Mpfr {
+        let values: Vec<&Mpfr> = iter.collect();
+        let prec = values.iter()
+            .map(|x| x.get_prec())
+            .max()
+            .unwrap_or_else(Mpfr::get_default_prec);
+        Mpfr::sum(&values, prec, Round::Nearest)
+    }
+}
+
+impl Product for Mpfr {
+    fn product<I: Iterator<Item = Mpfr>>(iter: I) -> Mpfr {
+        let values: Vec<Mpfr> = iter.collect();
+        values.iter().product()
+    }
+}
+
+impl<'a> Product<&'a Mpfr> for Mpfr {
+    fn product<I: Iterator<Item = &'a Mpfr>>(iter: I) -> Mpfr {
+        let values: Vec<&Mpfr> = iter.collect();
+        let prec = values.iter()
+            .map(|x| x.get_prec())
+            .max()
+            .unwrap_or_else(Mpfr::get_default_prec);
+        let one = Mpfr::from_i64_ternary(prec, 1, Round::Nearest).0;
+        values.into_iter().fold(one, |acc, x| acc * x)
+    }
+}
+
 // Negation
 
 impl<'b> Neg for &'b Mpfr {
@@ -1200,7 +2636,7 @@ impl<'b> Neg for &'b Mpfr {
     fn neg(self) -> Mpfr {
         unsafe {
             let mut res = Mpfr::new2(self.get_prec());
-            mpfr_neg(&mut res.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
+            mpfr_neg(&mut res.mpfr, &self.mpfr, Round::Nearest);
             res
         }
     }
@@ -1211,14 +2647,374 @@ impl Neg for Mpfr {
     #[inline]
     fn neg(mut self) -> Mpfr {
         unsafe {
-            mpfr_neg(&mut self.mpfr, &self.mpfr, mpfr_rnd_t::MPFR_RNDN);
+            mpfr_neg(&mut self.mpfr, &self.mpfr, Round::Nearest);
+            self
+        }
+    }
+}
+
+// Shifts
+//
+// x << n and x >> n multiply and divide by 2^n. They are exact unless the
+// result overflows or underflows.
+
+impl Shl<i64> for Mpfr {
+    type Output = Mpfr;
+    #[inline]
+    fn shl(mut self, other: i64) -> Mpfr {
+        unsafe {
+            mpfr_mul_2si(&mut self.mpfr, &self.mpfr, other as c_long, Round::Nearest);
+            self
+        }
+    }
+}
+
+impl<'a> Shl<i64> for &'a Mpfr {
+    type Output = Mpfr;
+    fn shl(self, other: i64) -> Mpfr {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            mpfr_mul_2si(&mut res.mpfr, &self.mpfr, other as c_long, Round::Nearest);
+            res
+        }
+    }
+}
+
+impl Shr<i64> for Mpfr {
+    type Output = Mpfr;
+    #[inline]
+    fn shr(mut self, other: i64) -> Mpfr {
+        unsafe {
+            mpfr_div_2si(&mut self.mpfr, &self.mpfr, other as c_long, Round::Nearest);
             self
         }
     }
 }
 
+impl<'a> Shr<i64> for &'a Mpfr {
+    type Output = Mpfr;
+    fn shr(self, other: i64) -> Mpfr {
+        unsafe {
+            let mut res = Mpfr::new2(self.get_prec());
+            mpfr_div_2si(&mut res.mpfr, &self.mpfr, other as c_long, Round::Nearest);
+            res
+        }
+    }
+}
+
 gen_overloads!(Mpfr);
 
+// Exception flags
+//
+// MPFR keeps a set of sticky flags (per thread, if MPFR was built with TLS)
+// that are raised by operations and stay raised until explicitly cleared.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Flags {
+    bits: mpfr_flags_t,
+}
+
+impl Flags {
+    pub const UNDERFLOW: Flags = Flags { bits: 1 };
+    pub const OVERFLOW: Flags = Flags { bits: 2 };
+    pub const NAN: Flags = Flags { bits: 4 };
+    pub const INEXACT: Flags = Flags { bits: 8 };
+    pub const ERANGE: Flags = Flags { bits: 16 };
+    pub const DIVBY0: Flags = Flags { bits: 32 };
+    pub const ALL: Flags = Flags { bits: 63 };
+
+    pub fn empty() -> Flags {
+        Flags { bits: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn contains(&self, other: Flags) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    pub fn underflow(&self) -> bool {
+        self.contains(Flags::UNDERFLOW)
+    }
+
+    pub fn overflow(&self) -> bool {
+        self.contains(Flags::OVERFLOW)
+    }
+
+    pub fn nan(&self) -> bool {
+        self.contains(Flags::NAN)
+    }
+
+    pub fn inexact(&self) -> bool {
+        self.contains(Flags::INEXACT)
+    }
+
+    pub fn erange(&self) -> bool {
+        self.contains(Flags::ERANGE)
+    }
+
+    pub fn divby0(&self) -> bool {
+        self.contains(Flags::DIVBY0)
+    }
+
+    // Returns the currently raised flags
+    pub fn save() -> Flags {
+        unsafe { Flags { bits: mpfr_flags_save() } }
+    }
+
+    // Makes the raised flags exactly equal to `self`
+    pub fn restore(self) {
+        unsafe { mpfr_flags_restore(self.bits, Flags::ALL.bits) }
+    }
+
+    // Returns which of the flags in `mask` are currently raised
+    pub fn test(mask: Flags) -> Flags {
+        unsafe { Flags { bits: mpfr_flags_test(mask.bits) } }
+    }
+
+    pub fn clear(mask: Flags) {
+        unsafe { mpfr_flags_clear(mask.bits) }
+    }
+
+    pub fn clear_all() {
+        Flags::clear(Flags::ALL)
+    }
+
+    pub fn raise(mask: Flags) {
+        unsafe { mpfr_flags_set(mask.bits) }
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+    fn bitor(self, other: Flags) -> Flags {
+        Flags { bits: self.bits | other.bits }
+    }
+}
+
+impl BitAnd for Flags {
+    type Output = Flags;
+    fn bitand(self, other: Flags) -> Flags {
+        Flags { bits: self.bits & other.bits }
+    }
+}
+
+impl Not for Flags {
+    type Output = Flags;
+    fn not(self) -> Flags {
+        Flags { bits: !self.bits & Flags::ALL.bits }
+    }
+}
+
+// Raises the saved flags again when dropped, even if with_flags unwinds
+struct FlagsGuard {
+    saved: Flags,
+}
+
+impl Drop for FlagsGuard {
+    fn drop(&mut self) {
+        (self.saved | Flags::save()).restore();
+    }
+}
+
+impl Mpfr {
+    // Runs `f` with all flags cleared and returns its result together with
+    // the flags it raised. The flags raised before the call stay raised.
+    pub fn with_flags<T, F: FnOnce() -> T>(f: F) -> (T, Flags) {
+        let _guard = FlagsGuard { saved: Flags::save() };
+        Flags::clear_all();
+        let res = f();
+        (res, Flags::save())
+    }
+}
+
+// Exponent range
+//
+// Like the flags, the exponent range is global (per thread) MPFR state.
+// ExpRange changes it and restores the previous range when dropped.
+
+pub struct ExpRange {
+    old_emin: mpfr_exp_t,
+    old_emax: mpfr_exp_t,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ExpRange {
+    pub fn new(emin: i64, emax: i64) -> Option<ExpRange> {
+        // MPFR checks each bound against its own limits, but not against
+        // each other
+        if emin > emax {
+            return None;
+        }
+        unsafe {
+            let range = ExpRange {
+                old_emin: mpfr_get_emin(),
+                old_emax: mpfr_get_emax(),
+                _not_send: PhantomData,
+            };
+            if mpfr_set_emin(emin as mpfr_exp_t) != 0 || mpfr_set_emax(emax as mpfr_exp_t) != 0 {
+                // Dropping the guard undoes a partial update
+                return None;
+            }
+            Some(range)
+        }
+    }
+
+    pub fn ieee(format: IeeeFormat) -> ExpRange {
+        ExpRange::new(format.emin(), format.emax()).expect("IEEE exponent range not supported")
+    }
+}
+
+impl Drop for ExpRange {
+    fn drop(&mut self) {
+        unsafe {
+            mpfr_set_emin(self.old_emin);
+            mpfr_set_emax(self.old_emax);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IeeeFormat {
+    Binary16,
+    Binary32,
+    Binary64,
+    Binary128,
+}
+
+impl IeeeFormat {
+    pub fn precision(&self) -> usize {
+        match *self {
+            IeeeFormat::Binary16 => 11,
+            IeeeFormat::Binary32 => 24,
+            IeeeFormat::Binary64 => 53,
+            IeeeFormat::Binary128 => 113,
+        }
+    }
+
+    // MPFR significands are in [0.5, 1), so emax is one more than the IEEE one
+    pub fn emax(&self) -> i64 {
+        match *self {
+            IeeeFormat::Binary16 => 16,
+            IeeeFormat::Binary32 => 128,
+            IeeeFormat::Binary64 => 1024,
+            IeeeFormat::Binary128 => 16384,
+        }
+    }
+
+    // Exponent of the smallest subnormal, in MPFR's convention
+    pub fn emin(&self) -> i64 {
+        4 - self.emax() - self.precision() as i64
+    }
+}
+
+impl Mpfr {
+    pub fn get_emin() -> i64 {
+        unsafe { mpfr_get_emin() as i64 }
+    }
+
+    pub fn get_emax() -> i64 {
+        unsafe { mpfr_get_emax() as i64 }
+    }
+
+    // Rounds `self` into the current exponent range, raising overflow or
+    // underflow as needed. `t` is the ternary value of the operation that
+    // produced `self`; the new ternary value is returned.
+    pub fn check_range(&mut self, t: Ordering, rnd: Round) -> Ordering {
+        unsafe { ternary(mpfr_check_range(&mut self.mpfr, t as c_int, rnd)) }
+    }
+
+    // Rounds `self` as if it were a subnormal in a format whose smallest
+    // subnormal is 2^(emin-1), see ExpRange::ieee.
+    pub fn subnormalize(&mut self, t: Ordering, rnd: Round) -> Ordering {
+        unsafe { ternary(mpfr_subnormalize(&mut self.mpfr, t as c_int, rnd)) }
+    }
+}
+
+// Random numbers
+//
+// gmp::rand::RandState does not give access to the underlying
+// gmp_randstate_t, so MPFR's generators take this equivalent wrapper. With
+// the same seed, the same sequence of calls gives the same values.
+
+pub struct RandState {
+    state: gmp_randstate_struct,
+}
+
+impl RandState {
+    pub fn new() -> RandState {
+        unsafe {
+            let mut state = MaybeUninit::<gmp_randstate_struct>::uninit();
+            __gmp_randinit_default(state.as_mut_ptr());
+            RandState { state: state.assume_init() }
+        }
+    }
+
+    pub fn new_mt() -> RandState {
+        unsafe {
+            let mut state = MaybeUninit::<gmp_randstate_struct>::uninit();
+            __gmp_randinit_mt(state.as_mut_ptr());
+            RandState { state: state.assume_init() }
+        }
+    }
+
+    pub fn seed(&mut self, seed: &Mpz) {
+        unsafe { __gmp_randseed(&mut self.state, seed.inner()) }
+    }
+
+    pub fn seed_ui(&mut self, seed: u64) {
+        unsafe { __gmp_randseed_ui(&mut self.state, seed as c_ulong) }
+    }
+}
+
+impl Default for RandState {
+    fn default() -> RandState {
+        RandState::new()
+    }
+}
+
+impl Clone for RandState {
+    fn clone(&self) -> RandState {
+        unsafe {
+            let mut state = MaybeUninit::<gmp_randstate_struct>::uninit();
+            __gmp_randinit_set(state.as_mut_ptr(), &self.state);
+            RandState { state: state.assume_init() }
+        }
+    }
+}
+
+impl Drop for RandState {
+    fn drop(&mut self) {
+        unsafe { __gmp_randclear(&mut self.state) }
+    }
+}
+
+impl Mpfr {
+    // Uniform in [0, 1), using exactly `precision` random bits. Returns None
+    // if the result does not fit the current exponent range.
+    pub fn urandomb(precision: usize, state: &mut RandState) -> Option<Mpfr> {
+        unsafe {
+            let mut res = Mpfr::new2(precision);
+            if mpfr_urandomb(&mut res.mpfr, &mut state.state) == 0 {
+                Some(res)
+            } else {
+                None
+            }
+        }
+    }
+
+    // Uniform in [0, 1], rounded from a real number drawn uniformly
+    random_fn!(urandom, urandom_round, urandom_ternary, mpfr_urandom);
+
+    // Standard normal distribution (mean 0, variance 1)
+    random_fn!(nrandom, nrandom_round, nrandom_ternary, mpfr_nrandom);
+
+    // Exponential distribution with mean 1
+    random_fn!(erandom, erandom_round, erandom_ternary, mpfr_erandom);
+}
+
 // rustc_serialize support
 impl Decodable for Mpfr {
     fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
@@ -1,5 +1,9 @@
 use rustc_serialize::json;
-use super::mpfr::Mpfr;
+use super::mpfr::{ExpRange, Flags, IeeeFormat, Mpfr, RandState, Round, Sign, TryFromMpfrError};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::panic;
+use gmp::mpq::Mpq;
 use gmp::mpz::Mpz;
 
 #[test]
@@ -34,6 +38,216 @@ fn test_ord() {
     assert!(z >= z && z >= y && z >= x && y >= x);
 }
 
+#[test]
+fn test_nan_comparison() {
+    let nan = Mpfr::nan();
+    let five: Mpfr = From::<i64>::from(5);
+
+    assert!(nan != nan);
+    assert!(nan != five);
+    assert!(five != nan);
+    assert!([nan < five, nan > five, nan <= five, nan >= five].iter().all(|&b| !b));
+    assert_eq!(nan.partial_cmp(&five), None);
+    assert_eq!(five.partial_cmp(&nan), None);
+    assert_eq!(five.partial_cmp(&five), Some(Ordering::Equal));
+    assert!(Mpfr::zero(-1) == Mpfr::zero(1));
+}
+
+#[test]
+fn test_total_cmp() {
+    let neg_nan = -Mpfr::nan();
+    let nan = Mpfr::nan();
+    let neg_inf = Mpfr::inf(-1);
+    let inf = Mpfr::inf(1);
+    let neg_zero = Mpfr::zero(-1);
+    let zero = Mpfr::zero(1);
+    let neg_one: Mpfr = From::<i64>::from(-1);
+    let one: Mpfr = From::<i64>::from(1);
+
+    let sorted = [&neg_nan, &neg_inf, &neg_one, &neg_zero, &zero, &one, &inf, &nan];
+    for (i, a) in sorted.iter().enumerate() {
+        for (j, b) in sorted.iter().enumerate() {
+            assert_eq!(a.total_cmp(b), i.cmp(&j));
+        }
+    }
+
+    let mut values = [one.clone(), nan.clone(), neg_zero.clone(), neg_inf.clone(), zero.clone()];
+    values.sort_by(|a, b| a.total_cmp(b));
+    assert_eq!(values[0].total_cmp(&neg_inf), Ordering::Equal);
+    assert_eq!(values[1].total_cmp(&neg_zero), Ordering::Equal);
+    assert_eq!(values[2].total_cmp(&zero), Ordering::Equal);
+    assert_eq!(values[4].total_cmp(&nan), Ordering::Equal);
+}
+
+#[test]
+fn test_classification() {
+    let nan = Mpfr::nan();
+    let inf = Mpfr::inf(-1);
+    let zero = Mpfr::zero(1);
+    let half: Mpfr = From::<f64>::from(0.5);
+    let two: Mpfr = From::<i64>::from(2);
+
+    assert!(nan.is_nan() && !nan.is_infinite() && !nan.is_finite());
+    assert!(inf.is_infinite() && !inf.is_finite() && !inf.is_nan());
+    assert!(zero.is_zero() && zero.is_finite() && !zero.is_regular());
+    assert!(half.is_regular() && !half.is_integer());
+    assert!(two.is_integer() && zero.is_integer());
+    assert!(!nan.is_integer() && !inf.is_integer());
+}
+
+#[test]
+fn test_sign() {
+    let one: Mpfr = From::<i64>::from(1);
+    let neg_two: Mpfr = From::<i64>::from(-2);
+    let neg_zero = Mpfr::zero(-1);
+
+    assert_eq!(one.sign(), Sign::Positive);
+    assert_eq!(neg_two.sign(), Sign::Negative);
+    assert_eq!(neg_zero.sign(), Sign::Negative);
+    assert!(neg_zero.is_sign_negative() && !Mpfr::zero(1).is_sign_negative());
+    assert!((-Mpfr::nan()).is_sign_negative());
+
+    assert_eq!(one.copysign(&neg_zero), From::<i64>::from(-1));
+    assert_eq!(neg_two.copysign(&one), From::<i64>::from(2));
+
+    let mut x = one.clone();
+    x.set_sign(Sign::Negative);
+    assert_eq!(x, From::<i64>::from(-1));
+    x.set_sign(Sign::Positive);
+    assert_eq!(x, one);
+
+    assert_eq!(neg_two.signum(), From::<i64>::from(-1));
+    assert_eq!(Mpfr::inf(1).signum(), one);
+    assert!(neg_zero.signum().is_sign_negative());
+    assert!(Mpfr::nan().signum().is_nan());
+
+    let nan = Mpfr::new2(200);
+    assert!(nan.is_nan());
+    assert_eq!(nan.signum().get_prec(), 200);
+    assert_eq!(neg_two.signum().get_prec(), neg_two.get_prec());
+}
+
+#[test]
+fn test_exp() {
+    let mut x: Mpfr = From::<f64>::from(12.0);
+    assert_eq!(x.get_exp(), Some(4));
+    assert_eq!(Mpfr::zero(1).get_exp(), None);
+    assert_eq!(Mpfr::nan().get_exp(), None);
+
+    assert!(x.set_exp(6));
+    assert_eq!(x, From::<f64>::from(48.0));
+    assert!(!Mpfr::inf(1).set_exp(6));
+
+    let (m, e) = Into::<Mpfr>::into(-12.0f64).frexp();
+    assert_eq!(m, From::<f64>::from(-0.75));
+    assert_eq!(e, 4);
+    assert_eq!(Mpfr::zero(-1).frexp().1, 0);
+
+    // Exponents beyond the i32 range, within a widened exponent range
+    let _range = ExpRange::new(-(1 << 50), 1 << 50).unwrap();
+    let big = (1i64 << 40) + 3;
+    let y = m.ldexp(big);
+    assert_eq!(y.get_exp(), Some(big));
+    assert_eq!(y.ldexp(4 - big), From::<f64>::from(-12.0));
+    assert_eq!(m.ldexp_ternary(1 << 51, Round::Nearest).0, Mpfr::inf(-1));
+}
+
+#[test]
+fn test_shift() {
+    let x: Mpfr = From::<f64>::from(3.0);
+    assert_eq!(&x << 3, From::<f64>::from(24.0));
+    assert_eq!(&x >> 2, From::<f64>::from(0.75));
+    assert_eq!(&x << -1, From::<f64>::from(1.5));
+    assert_eq!(x.clone() >> -4, From::<f64>::from(48.0));
+
+    let y = Mpfr::new2_from_str(200, "1.1", 10).unwrap();
+    let z = (&y << 1000) >> 1000;
+    assert_eq!(z, y);
+    assert_eq!(z.get_prec(), 200);
+}
+
+#[test]
+fn test_next() {
+    let one = Mpfr::new2_from_str(10, "1", 10).unwrap();
+    let up = one.next_up();
+    let down = one.next_down();
+    assert_eq!(up, Mpfr::new2_from_str(10, "1.001953125", 10).unwrap());
+    assert_eq!(down, Mpfr::new2_from_str(10, "0.9990234375", 10).unwrap());
+    assert_eq!(up.get_prec(), 10);
+    assert_eq!(up.next_down(), one);
+
+    assert_eq!(one.next_toward(&Mpfr::inf(-1)), down);
+    assert_eq!(one.next_toward(&one), one);
+    assert!(one.next_toward(&Mpfr::nan()).is_nan());
+
+    let tiny = Mpfr::zero(-1).next_up();
+    assert!(tiny > Mpfr::zero(1));
+    assert_eq!(tiny.next_down(), Mpfr::zero(1));
+    assert!(Mpfr::nan().next_up().is_nan());
+}
+
+#[test]
+fn test_ulp() {
+    let one = Mpfr::new2_from_str(10, "1", 10).unwrap();
+    assert_eq!(one.ulp(), Mpfr::new_u64_2exp(1, -9));
+    assert_eq!(one.next_down().ulp(), Mpfr::new_u64_2exp(1, -10));
+    assert_eq!(&one.next_up() - &one, one.ulp());
+
+    let x: Mpfr = From::<f64>::from(-3.0);
+    assert_eq!(x.ulp(), From::<f64>::from(2.0f64.powi(-51)));
+
+    let zero = Mpfr::zero(-1);
+    assert_eq!(zero.ulp(), Mpfr::zero(1).next_up());
+    assert_eq!(Mpfr::inf(-1).ulp(), Mpfr::inf(1));
+    assert!(Mpfr::nan().ulp().is_nan());
+}
+
+#[test]
+fn test_ulp_subnormal() {
+    let _range = ExpRange::ieee(IeeeFormat::Binary64);
+    let tiny = f64::from_bits(1);
+    let ulp = |x: f64| Mpfr::from_f64_ternary(53, x, Round::Nearest).0.ulp();
+
+    assert_eq!(ulp(1.0), From::<f64>::from(2.0f64.powi(-52)));
+    assert_eq!(ulp(f64::MIN_POSITIVE), From::<f64>::from(tiny));
+    assert_eq!(ulp(f64::MIN_POSITIVE * 4.0), From::<f64>::from(tiny * 4.0));
+    assert_eq!(ulp(2.0f64.powi(-1070)), From::<f64>::from(tiny));
+    assert_eq!(ulp(tiny), From::<f64>::from(tiny));
+    assert_eq!(ulp(0.0), From::<f64>::from(tiny));
+}
+
+#[test]
+fn test_random() {
+    let mut state = RandState::new();
+    state.seed_ui(42);
+    let mut replay = state.clone();
+
+    let zero = Mpfr::zero(1);
+    let one: Mpfr = From::<i64>::from(1);
+    for _ in 0..100 {
+        let x = Mpfr::urandomb(200, &mut state).unwrap();
+        assert!(x >= zero && x < one);
+        assert_eq!(x.get_prec(), 200);
+        assert_eq!(x, Mpfr::urandomb(200, &mut replay).unwrap());
+
+        let (y, t) = Mpfr::urandom_ternary(300, &mut state, Round::Down);
+        assert!(y >= zero && y <= one);
+        assert_eq!(t, Ordering::Less);
+        assert_eq!(y, Mpfr::urandom_round(300, &mut replay, Round::Down));
+    }
+
+    for _ in 0..100 {
+        assert!(Mpfr::nrandom(100, &mut state).is_regular());
+        assert!(Mpfr::erandom(100, &mut state) > zero);
+    }
+
+    let mut a = RandState::new_mt();
+    let mut b = RandState::new_mt();
+    a.seed_ui(7);
+    b.seed_ui(7);
+    assert_eq!(Mpfr::nrandom(256, &mut a), Mpfr::nrandom(256, &mut b));
+}
+
 #[test]
 #[should_panic]
 fn test_div_zero() {
@@ -42,6 +256,16 @@ fn test_div_zero() {
     x / y;
 }
 
+#[test]
+fn test_div_nan() {
+    let one: Mpfr = From::<i64>::from(1);
+    let nan = Mpfr::nan();
+    assert!(one.div_ternary(&nan, Round::Nearest).0.is_nan());
+    assert!((&one / &nan).is_nan());
+    assert!((2.0 / &nan).is_nan());
+    assert!((2 / nan).is_nan());
+}
+
 #[test]
 fn test_clone() {
     let a: Mpfr = From::<i64>::from(100);
@@ -202,6 +426,184 @@ fn test_div_prec() {
     assert!((20.0 / &a).get_prec() == high_prec);
 }
 
+#[test]
+fn test_fused() {
+    // With 53 bits, (1 + 2^-30)^2 - 1 loses the 2^-60 term when rounded twice
+    let x = Mpfr::new_u64_2exp((1 << 30) + 1, -30);
+    let exact = Mpfr::new_u64_2exp((1 << 31) + 1, -60);
+    let one: Mpfr = From::<i64>::from(1);
+    let minus_one: Mpfr = From::<i64>::from(-1);
+
+    assert!(x.mul_add(&x, &minus_one) == exact);
+    assert!(x.mul_sub(&x, &one) == exact);
+    assert!(&x * &x - &one != exact);
+    assert!(Mpfr::fmms(&x, &x, &one, &one) == exact);
+    assert!(Mpfr::fmma(&x, &x, &minus_one, &one) == exact);
+    assert_eq!(x.mul_add_ternary(&x, &minus_one, Round::Nearest).1, Ordering::Equal);
+
+    let high = Mpfr::new2_from_str(128, "1", 10).unwrap();
+    assert_eq!(x.mul_add(&one, &high).get_prec(), 128);
+    assert_eq!(Mpfr::fmma(&x, &x, &one, &high).get_prec(), 128);
+}
+
+#[test]
+fn test_hypot() {
+    let three: Mpfr = From::<i64>::from(3);
+    let four: Mpfr = From::<i64>::from(4);
+    let five: Mpfr = From::<i64>::from(5);
+    assert!(three.hypot(&four) == five);
+
+    // No intermediate overflow
+    let big = Mpfr::new2_from_str(53, "1e300", 10).unwrap();
+    let (_, flags) = Mpfr::with_flags(|| big.hypot(&big));
+    assert!(!flags.overflow());
+}
+
+#[test]
+fn test_sum() {
+    let big = Mpfr::new2_from_str(53, "1e20", 10).unwrap();
+    let minus_big = -&big;
+    let one: Mpfr = From::<i64>::from(1);
+
+    // Folding with Add loses the 1 entirely
+    assert!(&(&big + &one) + &minus_big == Mpfr::zero(1));
+    assert!(Mpfr::sum(&[&big, &one, &minus_big], 53, Round::Nearest) == one);
+    assert!(Mpfr::sum(&[&one, &big, &minus_big], 53, Round::Nearest) == one);
+
+    let third: Mpfr = &one / 3;
+    let (s, t) = Mpfr::sum_ternary(&[&third, &third, &third], 2, Round::Down);
+    assert!(s < one);
+    assert_eq!(t, Ordering::Less);
+    assert_eq!(s.get_prec(), 2);
+    assert!(Mpfr::sum(&[], 53, Round::Nearest) == Mpfr::zero(1));
+}
+
+#[test]
+fn test_dot() {
+    let big = Mpfr::new2_from_str(53, "1e20", 10).unwrap();
+    let one: Mpfr = From::<i64>::from(1);
+    let minus_one: Mpfr = From::<i64>::from(-1);
+    let two: Mpfr = From::<i64>::from(2);
+
+    let a = [&big, &one, &big];
+    let b = [&one, &two, &minus_one];
+    assert!(Mpfr::dot(&a, &b, 53, Round::Nearest) == two);
+    assert_eq!(Mpfr::dot_ternary(&a, &b, 53, Round::Nearest).1, Ordering::Equal);
+    assert!(Mpfr::dot(&[], &[], 53, Round::Nearest) == Mpfr::zero(1));
+}
+
+#[test]
+fn test_iter_sum_product() {
+    let big = Mpfr::new2_from_str(53, "1e20", 10).unwrap();
+    let values = vec![big.clone(), From::<i64>::from(1), -&big];
+    let one: Mpfr = From::<i64>::from(1);
+
+    assert!(values.iter().sum::<Mpfr>() == one);
+    assert!(values.into_iter().sum::<Mpfr>() == one);
+
+    let high = Mpfr::new2_from_str(128, "3", 10).unwrap();
+    let factors = vec![From::<i64>::from(2), high.clone(), From::<i64>::from(4)];
+    let product: Mpfr = factors.iter().product();
+    assert!(product == From::<i64>::from(24));
+    assert_eq!(product.get_prec(), 128);
+    assert!(factors.into_iter().product::<Mpfr>() == From::<i64>::from(24));
+    assert_eq!([high].iter().sum::<Mpfr>().get_prec(), 128);
+
+    // Low-precision terms stay at their own precision, below the default
+    let three = Mpfr::new2_from_str(10, "3", 10).unwrap();
+    let low = vec![three.clone(), &three + 2];
+    assert_eq!(low.iter().sum::<Mpfr>().get_prec(), 10);
+    assert_eq!(low.iter().product::<Mpfr>().get_prec(), 10);
+    assert_eq!(low.into_iter().product::<Mpfr>(), From::<i64>::from(15));
+    assert_eq!(Vec::<Mpfr>::new().into_iter().product::<Mpfr>().get_prec(),
+               Mpfr::get_default_prec());
+}
+
+#[test]
+fn test_rem() {
+    let a: Mpfr = From::<f64>::from(7.5);
+    let b: Mpfr = From::<i64>::from(2);
+    let result: Mpfr = From::<f64>::from(1.5);
+
+    assert!(&a % &b == result);
+    assert!(&a % b.clone() == result);
+    assert!(a.clone() % &b == result);
+    assert!(a.clone() % b.clone() == result);
+    assert!(&a % 2 == result);
+    assert!(&a % 2.0 == result);
+    assert!(a.clone() % 2 == result);
+    assert!(a.clone() % 2.0 == result);
+    assert!(7.5 % &b == result);
+    assert!(7.5 % b.clone() == result);
+    assert!(9 % &a == result);
+    assert!(9 % a.clone() == result);
+
+    let neg: Mpfr = -&a;
+    assert!(&neg % &b == -&result);
+    assert!(&neg % 2 == -&result);
+}
+
+#[test]
+fn test_rem_prec() {
+    let high_prec = 128;
+    let a: Mpfr = Mpfr::new2_from_str(high_prec, "15", 10).unwrap();
+    let b: Mpfr = From::<i64>::from(4);
+
+    assert!((&a % &b).get_prec() == high_prec);
+    assert!((&b % &a).get_prec() == high_prec);
+    assert!((a.clone() % &b).get_prec() == high_prec);
+    assert!((b.clone() % &a).get_prec() == high_prec);
+    assert!((&a % 4).get_prec() == high_prec);
+    assert!((20 % &a).get_prec() == high_prec);
+    assert!((&a % 4.0).get_prec() == high_prec);
+    assert!((20.0 % &a).get_prec() == high_prec);
+}
+
+#[test]
+#[should_panic]
+fn test_rem_zero() {
+    let x: Mpfr = From::<i64>::from(1);
+    let _ = x % Mpfr::zero(1);
+}
+
+#[test]
+fn test_rem_nan() {
+    let x: Mpfr = From::<i64>::from(5);
+    let nan = Mpfr::nan();
+    assert!((&x % &nan).is_nan());
+    assert!(x.rem_ternary(&nan, Round::Nearest).0.is_nan());
+    assert!((2.0 % &nan).is_nan());
+    assert!((2 % nan.clone()).is_nan());
+    assert!((&nan % 2.0).is_nan());
+    assert!(x.remainder(&Mpfr::zero(1)).is_nan());
+}
+
+#[test]
+fn test_remainder_remquo() {
+    let prec = 200;
+    let a: Mpfr = From::<f64>::from(7.5);
+    let b: Mpfr = From::<i64>::from(2);
+
+    // 7.5 / 2 = 3.75 rounds to 4, so the remainder is -0.5
+    assert!(a.remainder(&b) == From::<f64>::from(-0.5));
+    let (r, q) = a.remquo(&b);
+    assert!(r == From::<f64>::from(-0.5));
+    assert_eq!(q & 7, 4);
+    let (_, q) = (-&a).remquo(&b);
+    assert_eq!(q, -4);
+
+    // Angle wrapping: 100 mod 2pi, compared with the exact reduction
+    let angle = Mpfr::new2_from_str(prec, "100", 10).unwrap();
+    let two_pi = Mpfr::pi(prec) * 2;
+    let wrapped = &angle % &two_pi;
+    let eps = Mpfr::new2_from_str(prec, "1e-55", 10).unwrap();
+    assert!((&wrapped - (&angle - &two_pi * 15)).abs() < eps);
+    assert!((wrapped.sin() - angle.sin()).abs() < eps);
+    let (r, q) = angle.remquo(&two_pi);
+    assert_eq!(q, 16);
+    assert!((r - (&angle - &two_pi * 16)).abs() < eps);
+}
+
 #[test]
 fn test_rounding() {
     let a: Mpfr = From::<f64>::from(2.4999);
@@ -218,6 +620,107 @@ fn test_rounding() {
     assert!(b.ceil() == three);
 }
 
+#[test]
+fn test_trunc_frac() {
+    let a: Mpfr = From::<f64>::from(-2.75);
+    let b: Mpfr = From::<f64>::from(2.5);
+    let c: Mpfr = From::<f64>::from(3.5);
+
+    assert!(a.trunc() == From::<i64>::from(-2));
+    assert!(a.frac() == From::<f64>::from(-0.75));
+    let (int, frac) = a.modf();
+    assert!(int == From::<i64>::from(-2) && frac == From::<f64>::from(-0.75));
+    let ((_, t_int), (_, t_frac)) = a.modf_ternary(Round::Nearest);
+    assert_eq!((t_int, t_frac), (Ordering::Equal, Ordering::Equal));
+
+    assert!(b.round() == From::<i64>::from(3));
+    assert!(b.roundeven() == From::<i64>::from(2));
+    assert!(c.roundeven() == From::<i64>::from(4));
+    assert_eq!(a.floor_ternary().1, Ordering::Less);
+    assert_eq!(a.ceil_ternary().1, Ordering::Greater);
+    assert_eq!(a.trunc().trunc_ternary().1, Ordering::Equal);
+}
+
+#[test]
+fn test_rint() {
+    let a: Mpfr = From::<f64>::from(2.5);
+    assert!(a.rint(53, Round::Nearest) == From::<i64>::from(2));
+    assert!(a.rint(53, Round::Up) == From::<i64>::from(3));
+    assert!(a.rint_floor(53, Round::Up) == From::<i64>::from(2));
+    assert!(a.rint_ceil(53, Round::Down) == From::<i64>::from(3));
+    assert!(a.rint_trunc(53, Round::Up) == From::<i64>::from(2));
+    assert!(a.rint_round(53, Round::Down) == From::<i64>::from(3));
+    assert!(a.rint_roundeven(53, Round::Up) == From::<i64>::from(2));
+    assert_eq!(a.rint(4, Round::Nearest).get_prec(), 4);
+
+    // 19.75 rounds down to the integer 19, which needs 5 bits; with 4 bits
+    // it is then rounded to 18 or 20 depending on the mode
+    let b: Mpfr = From::<f64>::from(19.75);
+    let (down, t) = b.rint_floor_ternary(4, Round::Down);
+    assert!(down == From::<i64>::from(18));
+    assert_eq!(t, Ordering::Less);
+    let (up, t) = b.rint_floor_ternary(4, Round::Up);
+    assert!(up == From::<i64>::from(20));
+    assert_eq!(t, Ordering::Greater);
+    assert!(b.rint_ceil(4, Round::Down) == From::<i64>::from(20));
+    assert!(b.rint_trunc(5, Round::Up) == From::<i64>::from(19));
+}
+
+#[test]
+fn test_round_modes() {
+    let one: Mpfr = From::<i64>::from(1);
+    let three: Mpfr = From::<i64>::from(3);
+    let two: Mpfr = From::<i64>::from(2);
+
+    let up = one.div_round(&three, Round::Up);
+    let down = one.div_round(&three, Round::Down);
+    assert!(down < up);
+    assert!(one.div_round(&three, Round::Zero) == down);
+    assert!(one.div_round(&three, Round::AwayZero) == up);
+    assert!(two.sqrt_round(Round::Down) < two.sqrt_round(Round::Up));
+    assert!(two.sqrt_round(Round::Nearest) == two.sqrt());
+
+    let neg_tenth = Mpfr::new2_from_str(100, "-0.1", 10).unwrap();
+    assert!(neg_tenth.to_f64_round(Round::Up) > neg_tenth.to_f64_round(Round::Down));
+    assert_eq!(neg_tenth.to_i64_round(Round::Down), -1);
+    assert_eq!(neg_tenth.to_i64_round(Round::Zero), 0);
+}
+
+#[test]
+fn test_set_round() {
+    let mut a = Mpfr::new2(2);
+    a.set_i64_round(7, Round::Down);
+    assert!(a == From::<i64>::from(6));
+    a.set_i64_round(7, Round::Up);
+    assert!(a == From::<i64>::from(8));
+
+    let b = Mpfr::new2_from_str_round(2, "0.3", 10, Round::Down).unwrap();
+    let c = Mpfr::new2_from_str_round(2, "0.3", 10, Round::Up).unwrap();
+    assert!(b == From::<f64>::from(0.25));
+    assert!(c == From::<f64>::from(0.375));
+}
+
+#[test]
+fn test_ternary() {
+    let one: Mpfr = From::<i64>::from(1);
+    let two: Mpfr = From::<i64>::from(2);
+    let three: Mpfr = From::<i64>::from(3);
+    let four: Mpfr = From::<i64>::from(4);
+
+    assert_eq!(one.add_ternary(&two, Round::Nearest).1, Ordering::Equal);
+    assert_eq!(one.div_ternary(&three, Round::Up).1, Ordering::Greater);
+    assert_eq!(one.div_ternary(&three, Round::Down).1, Ordering::Less);
+    assert_eq!(four.sqrt_ternary(Round::Down), (two.clone(), Ordering::Equal));
+    assert_eq!(two.sqrt_ternary(Round::Zero).1, Ordering::Less);
+    assert_eq!(two.pow_ternary(&three, Round::Nearest).1, Ordering::Equal);
+
+    let (a, t) = Mpfr::from_f64_ternary(2, 0.3, Round::Up);
+    assert!(a == From::<f64>::from(0.375));
+    assert_eq!(t, Ordering::Greater);
+    assert_eq!(Mpfr::from_i64_ternary(64, -7, Round::Nearest).1, Ordering::Equal);
+    assert_eq!(Mpfr::from_u64_ternary(2, 7, Round::Down).1, Ordering::Less);
+}
+
 #[test]
 fn test_pow_root() {
     let a: Mpfr = From::<f64>::from(2.654);
@@ -243,6 +746,228 @@ fn test_exp_log() {
     assert!(b.log() == a);
 }
 
+#[test]
+fn test_exp_log_variants() {
+    let eight: Mpfr = From::<i64>::from(8);
+    let three: Mpfr = From::<i64>::from(3);
+    let thousand: Mpfr = From::<i64>::from(1000);
+
+    assert!(eight.log2() == three);
+    assert!(thousand.log10() == three);
+    assert!(three.exp2() == eight);
+    assert!(three.exp10() == thousand);
+    assert!(Mpfr::log_ui(8, 53) == eight.log());
+    assert_eq!(Mpfr::log_ui(8, 100).get_prec(), 100);
+
+    // log1p and expm1 keep full relative precision for tiny arguments
+    let tiny = Mpfr::new2_from_str(53, "1e-20", 10).unwrap();
+    let eps = Mpfr::new2_from_str(53, "1e-35", 10).unwrap();
+    assert!((tiny.log1p() - &tiny).abs() < eps);
+    assert!((tiny.expm1() - &tiny).abs() < eps);
+    assert!((&tiny + 1).log() == Mpfr::zero(1));
+    assert!(tiny.log1p().log1p_round(Round::Down) < tiny);
+}
+
+#[cfg(feature = "mpfr_4_2")]
+#[test]
+fn test_exp_log_mpfr_4_2() {
+    let seven: Mpfr = From::<i64>::from(7);
+    let three: Mpfr = From::<i64>::from(3);
+    let ninety_nine: Mpfr = From::<i64>::from(99);
+    let two: Mpfr = From::<i64>::from(2);
+
+    assert!(seven.log2p1() == three);
+    assert!(ninety_nine.log10p1() == two);
+    assert!(three.exp2m1() == seven);
+    assert!(two.exp10m1() == ninety_nine);
+
+    let rate: Mpfr = From::<f64>::from(0.5);
+    assert!(rate.compound(2) == From::<f64>::from(2.25));
+}
+
+#[test]
+fn test_lgamma() {
+    let prec = 128;
+    let five = Mpfr::new2_from_str(prec, "5", 10).unwrap();
+    let (lg, sign) = five.lgamma();
+    assert!(lg == five.lngamma());
+    assert_eq!(sign, Sign::Positive);
+    assert_eq!(lg.get_prec(), prec);
+}
+
+#[test]
+fn test_lgamma_negative() {
+    let prec = 128;
+    let eps = Mpfr::new2_from_str(prec, "1e-35", 10).unwrap();
+
+    // Gamma(-0.5) = -2 sqrt(pi), Gamma(-1.5) = 4/3 sqrt(pi)
+    let x = Mpfr::new2_from_str(prec, "-0.5", 10).unwrap();
+    let (lg, sign) = x.lgamma();
+    assert_eq!(sign, Sign::Negative);
+    assert!((lg - x.gamma().abs().log()).abs() < eps);
+
+    let y = Mpfr::new2_from_str(prec, "-1.5", 10).unwrap();
+    let (lg, sign) = y.lgamma();
+    assert_eq!(sign, Sign::Positive);
+    assert!((lg - y.gamma().log()).abs() < eps);
+
+    let z = Mpfr::new2_from_str(prec, "-2.5", 10).unwrap();
+    let (lg, sign, t) = z.lgamma_ternary(Round::Up);
+    assert_eq!(sign, Sign::Negative);
+    assert_eq!(t, Ordering::Greater);
+    assert!((lg - z.gamma().abs().log()).abs() < eps);
+}
+
+#[test]
+fn test_special_functions() {
+    let prec = 200;
+    let zero = Mpfr::zero(1);
+    let one = Mpfr::new2_from_str(prec, "1", 10).unwrap();
+    let two = Mpfr::new2_from_str(prec, "2", 10).unwrap();
+    let three = Mpfr::new2_from_str(prec, "3", 10).unwrap();
+    let x = Mpfr::new2_from_str(prec, "0.6", 10).unwrap();
+    let eps = Mpfr::new2_from_str(prec, "1e-55", 10).unwrap();
+    let pi = (-&one).acos();
+    let pi_sq = &pi * &pi;
+
+    assert!(zero.erf() == zero);
+    assert!(zero.erfc() == one);
+    assert!((x.erf() + x.erfc() - &one).abs() < eps);
+
+    assert!((two.zeta() - &pi_sq / 6).abs() < eps);
+    assert!(Mpfr::zeta_ui(2, prec) == two.zeta());
+    assert_eq!(Mpfr::zeta_ui(2, 64).get_prec(), 64);
+
+    assert!((one.li2() - &pi_sq / 6).abs() < eps);
+    assert!(one.digamma() < zero && two.digamma() > zero);
+    assert!((two.digamma() - one.digamma() - &one).abs() < eps);
+    assert!(one.eint() > one);
+    assert!(zero.ai() > zero && three.ai() < x.ai());
+
+    // B(2, 3) = 1! 2! / 4! = 1/12
+    assert!((two.beta(&three) - &one / 12).abs() < eps);
+    assert!(two.beta(&three) == three.beta(&two));
+}
+
+#[test]
+fn test_bessel() {
+    let prec = 200;
+    let x = Mpfr::new2_from_str(prec, "2.5", 10).unwrap();
+    let eps = Mpfr::new2_from_str(prec, "1e-55", 10).unwrap();
+
+    assert!(x.jn(0) == x.j0() && x.jn(1) == x.j1());
+    assert!(x.yn(0) == x.y0() && x.yn(1) == x.y1());
+    assert_eq!(x.jn(5).get_prec(), prec);
+
+    // Recurrence: J(n-1)(x) + J(n+1)(x) = 2n/x J(n)(x), same for Y
+    assert!((x.jn(2) + x.jn(4) - x.jn(3) * 6 / &x).abs() < eps);
+    assert!((x.yn(2) + x.yn(4) - x.yn(3) * 6 / &x).abs() < eps);
+
+    // First zero of J0 lies between 2.404 and 2.405
+    let a = Mpfr::new2_from_str(prec, "2.404", 10).unwrap();
+    let b = Mpfr::new2_from_str(prec, "2.405", 10).unwrap();
+    assert!(a.j0() > Mpfr::zero(1) && b.j0() < Mpfr::zero(1));
+    assert!(x.j0_round(Round::Down) < x.j0_round(Round::Up));
+}
+
+#[test]
+fn test_constants() {
+    let pi_str = "3.14159265358979323846264338327950288419716939937510582097494459230781640628620899";
+    let pi = Mpfr::pi(256);
+    assert_eq!(pi.get_prec(), 256);
+    assert!(pi == Mpfr::new2_from_str(256, pi_str, 10).unwrap());
+    assert!(Mpfr::pi(53) == From::<f64>::from(::std::f64::consts::PI));
+    assert!(Mpfr::pi_round(100, Round::Down) < Mpfr::pi_round(100, Round::Up));
+    assert_eq!(Mpfr::pi_ternary(100, Round::Up).1, Ordering::Greater);
+
+    let two: Mpfr = From::<i64>::from(2);
+    assert!(Mpfr::ln2(53) == two.log());
+    assert!(Mpfr::ln2(53) == From::<f64>::from(::std::f64::consts::LN_2));
+
+    let euler = Mpfr::new2_from_str(64, "0.5772156649015328606065120900824024310422", 10).unwrap();
+    assert!(Mpfr::euler(64) == euler);
+    let catalan = Mpfr::new2_from_str(64, "0.9159655941772190150546035149323841107741", 10).unwrap();
+    assert!(Mpfr::catalan(64) == catalan);
+}
+
+#[test]
+fn test_trig() {
+    let prec = 200;
+    let x = Mpfr::new2_from_str(prec, "0.7", 10).unwrap();
+    let one = Mpfr::new2_from_str(prec, "1", 10).unwrap();
+    let eps = Mpfr::new2_from_str(prec, "1e-55", 10).unwrap();
+
+    let (sin, cos) = x.sin_cos();
+    assert!(sin == x.sin() && cos == x.cos());
+    assert_eq!(sin.get_prec(), prec);
+    assert!((&sin * &sin + &cos * &cos - &one).abs() < eps);
+    assert!((&sin / &cos - x.tan()).abs() < eps);
+    assert!((&one / &cos - x.sec()).abs() < eps);
+    assert!((&one / &sin - x.csc()).abs() < eps);
+    assert!((&cos / &sin - x.cot()).abs() < eps);
+    assert!(x.sin_round(Round::Down) < x.sin_round(Round::Up));
+
+    let zero = Mpfr::zero(1);
+    let ((s, ts), (c, tc)) = zero.sin_cos_ternary(Round::Nearest);
+    assert!(s == zero && c == From::<i64>::from(1));
+    assert_eq!((ts, tc), (Ordering::Equal, Ordering::Equal));
+    let ((_, ts), (_, tc)) = x.sin_cos_ternary(Round::Up);
+    assert_eq!((ts, tc), (Ordering::Greater, Ordering::Greater));
+}
+
+#[test]
+fn test_inverse_trig() {
+    let prec = 200;
+    let x = Mpfr::new2_from_str(prec, "0.3", 10).unwrap();
+    let eps = Mpfr::new2_from_str(prec, "1e-55", 10).unwrap();
+
+    assert!((x.asin().sin() - &x).abs() < eps);
+    assert!((x.acos().cos() - &x).abs() < eps);
+    assert!((x.atan().tan() - &x).abs() < eps);
+    assert_eq!(x.atan().get_prec(), prec);
+}
+
+#[test]
+fn test_atan2() {
+    let one: Mpfr = From::<i64>::from(1);
+    let minus_one: Mpfr = From::<i64>::from(-1);
+    let high = Mpfr::new2_from_str(128, "1", 10).unwrap();
+    let pi = minus_one.acos();
+
+    assert!(one.atan2(&one) == one.atan());
+    assert!(one.atan2(&minus_one) == &pi * 0.75);
+    assert!(minus_one.atan2(&minus_one) == &pi * -0.75);
+    assert!(Mpfr::zero(1).atan2(&minus_one) == pi);
+    assert!(Mpfr::zero(-1).atan2(&minus_one) == -&pi);
+    assert_eq!(one.atan2(&high).get_prec(), 128);
+    assert_eq!(high.atan2(&one).get_prec(), 128);
+}
+
+#[test]
+fn test_hyperbolic() {
+    let prec = 200;
+    let x = Mpfr::new2_from_str(prec, "0.4", 10).unwrap();
+    let one = Mpfr::new2_from_str(prec, "1", 10).unwrap();
+    let eps = Mpfr::new2_from_str(prec, "1e-55", 10).unwrap();
+
+    let (sinh, cosh) = x.sinh_cosh();
+    assert!(sinh == x.sinh() && cosh == x.cosh());
+    assert!((&cosh * &cosh - &sinh * &sinh - &one).abs() < eps);
+    assert!((&sinh / &cosh - x.tanh()).abs() < eps);
+    assert!((&one / &cosh - x.sech()).abs() < eps);
+    assert!((&one / &sinh - x.csch()).abs() < eps);
+    assert!((&cosh / &sinh - x.coth()).abs() < eps);
+
+    assert!((x.sinh().asinh() - &x).abs() < eps);
+    assert!((x.cosh().acosh() - &x).abs() < eps);
+    assert!((x.tanh().atanh() - &x).abs() < eps);
+
+    // No cancellation for tiny arguments
+    let tiny = Mpfr::new2_from_str(53, "1e-30", 10).unwrap();
+    assert!(tiny.sinh() == tiny);
+    assert!(tiny.asinh_round(Round::Down) < tiny);
+}
+
 #[test]
 fn test_new_from_str() {
     let a: Mpfr = From::<i64>::from(1);
@@ -314,7 +1039,7 @@ fn test_mpfr_set_prec() {
 
     a.set_prec(new_prec);
 
-    assert!(a == Mpfr::nan());
+    assert!(a.is_nan());
     assert!(a.get_prec() == new_prec);
 }
 
@@ -347,6 +1072,44 @@ fn test_to_string() {
     assert_eq!(a.to_string(), "1.23456789123456789123456789123456789e+05");
 }
 
+#[test]
+fn test_display_shortest() {
+    let tenth: Mpfr = From::<f64>::from(0.1);
+    assert_eq!(tenth.to_string(), "1.0000000000000001e-01");
+    assert_eq!(Mpfr::new_from_str(tenth.to_string(), 10).unwrap(), tenth);
+
+    let five: Mpfr = From::<i64>::from(5);
+    assert_eq!(format!("{}", five), "5e+00");
+    assert_eq!(format!("{:+}", five), "+5e+00");
+    assert_eq!(format!("{}", Mpfr::zero(-1)), "-0e+00");
+}
+
+#[test]
+fn test_display_precision() {
+    let x: Mpfr = From::<f64>::from(1234.5678);
+    assert_eq!(format!("{:.2}", x), "1234.57");
+    assert_eq!(format!("{:.0}", x), "1235");
+    assert_eq!(format!("{:.10}", Mpfr::pi(100)), "3.1415926536");
+    assert_eq!(format!("{:.3}", -&x), "-1234.568");
+    assert_eq!(format!("{:+.1}", x), "+1234.6");
+}
+
+#[test]
+fn test_display_padding() {
+    let x: Mpfr = From::<f64>::from(1234.5678);
+    assert_eq!(format!("{:>12.2}", x), "     1234.57");
+    assert_eq!(format!("{:12.2}", x), "     1234.57");
+    assert_eq!(format!("{:*<10.1}", x), "1234.6****");
+    assert_eq!(format!("{:^11.1}", x), "  1234.6   ");
+    assert_eq!(format!("{:011.2}", -&x), "-0001234.57");
+    assert_eq!(format!("{:>+10}", Mpfr::from(5i64)), "    +5e+00");
+
+    assert_eq!(format!("{}", Mpfr::nan()), "NaN");
+    assert_eq!(format!("{:>5}", Mpfr::nan()), "  NaN");
+    assert_eq!(format!("{:+}", Mpfr::inf(1)), "+inf");
+    assert_eq!(format!("{:.3}", Mpfr::inf(-1)), "-inf");
+}
+
 #[test]
 fn test_to_string_new2_from_str() {
     let a: Mpfr = Mpfr::new2_from_str(128, "1.23456789123456789123456789123456789e5", 10).unwrap();
@@ -357,17 +1120,160 @@ fn test_to_string_new2_from_str() {
 #[test]
 fn test_into() {
     let a: Mpfr = mpfr!(0.1234);
-    let b: i64 = (&a).into();
-    assert_eq!(b, 0);
+    assert_eq!(a.to_i64_round(Round::Nearest), 0);
     let c: f64 = (&a).into();
     assert_eq!(c, 0.1234);
-    let d: u64 = (&a).into();
-    assert_eq!(d, 0);
+    assert_eq!(a.to_u64_round(Round::Nearest), 0);
 
     let zero: Mpz = From::from(0i64);
     assert_eq!(Into::<Mpz>::into(&a), zero);
 }
 
+#[test]
+fn test_try_from_small() {
+    let int = |s: &str| Mpfr::new2_from_str(200, s, 10).unwrap();
+
+    assert_eq!(i8::try_from(&int("-128")), Ok(-128));
+    assert_eq!(i8::try_from(&int("128")), Err(TryFromMpfrError::Overflow));
+    assert_eq!(u8::try_from(&int("255")), Ok(255));
+    assert_eq!(u8::try_from(&int("-1")), Err(TryFromMpfrError::Overflow));
+    assert_eq!(i16::try_from(&int("-32769")), Err(TryFromMpfrError::Overflow));
+    assert_eq!(u16::try_from(&int("65535")), Ok(65535));
+    assert_eq!(i32::try_from(&int("2147483647")), Ok(i32::MAX));
+    assert_eq!(u32::try_from(&int("4294967296")), Err(TryFromMpfrError::Overflow));
+    assert_eq!(i64::try_from(&int("-9223372036854775808")), Ok(i64::MIN));
+    assert_eq!(i64::try_from(&int("9223372036854775808")), Err(TryFromMpfrError::Overflow));
+    assert_eq!(u64::try_from(&int("18446744073709551615")), Ok(u64::MAX));
+    assert_eq!(u64::try_from(&Mpfr::zero(-1)), Ok(0));
+}
+
+#[test]
+fn test_try_from_128() {
+    let int = |s: &str| Mpfr::new2_from_str(200, s, 10).unwrap();
+
+    assert_eq!(i128::try_from(&int("-170141183460469231731687303715884105728")),
+               Ok(i128::MIN));
+    assert_eq!(i128::try_from(&int("170141183460469231731687303715884105727")),
+               Ok(i128::MAX));
+    assert_eq!(i128::try_from(&int("170141183460469231731687303715884105728")),
+               Err(TryFromMpfrError::Overflow));
+    assert_eq!(i128::try_from(&int("-12345678901234567890123456789")),
+               Ok(-12345678901234567890123456789));
+    assert_eq!(u128::try_from(&int("340282366920938463463374607431768211455")),
+               Ok(u128::MAX));
+    assert_eq!(u128::try_from(&int("340282366920938463463374607431768211456")),
+               Err(TryFromMpfrError::Overflow));
+    assert_eq!(u128::try_from(&int("-1")), Err(TryFromMpfrError::Overflow));
+    assert_eq!(u128::try_from(&int("18446744073709551616")), Ok(1 << 64));
+}
+
+#[test]
+fn test_try_from_errors() {
+    let half: Mpfr = From::<f64>::from(2.5);
+    assert_eq!(i32::try_from(&Mpfr::nan()), Err(TryFromMpfrError::Nan));
+    assert_eq!(i64::try_from(&Mpfr::inf(1)), Err(TryFromMpfrError::Overflow));
+    assert_eq!(u128::try_from(&Mpfr::inf(-1)), Err(TryFromMpfrError::Overflow));
+    assert_eq!(u8::try_from(&half), Err(TryFromMpfrError::NotInteger));
+    assert_eq!(i128::try_from(&half), Err(TryFromMpfrError::NotInteger));
+
+    assert_eq!(half.to_int_checked::<i8>(Round::Down), Ok(2));
+    assert_eq!(half.to_int_checked::<u16>(Round::Up), Ok(3));
+    assert_eq!((-&half).to_int_checked::<i128>(Round::Zero), Ok(-2));
+    assert_eq!((-&half).to_int_checked::<u32>(Round::Down), Err(TryFromMpfrError::Overflow));
+    assert_eq!(Mpfr::nan().to_int_checked::<i64>(Round::Down), Err(TryFromMpfrError::Nan));
+}
+
+#[test]
+fn test_from_128() {
+    let max: Mpfr = From::<u128>::from(u128::MAX);
+    assert_eq!(max.get_prec(), 128);
+    let expected = Mpfr::new2_from_str(128, "340282366920938463463374607431768211455", 10);
+    assert_eq!(max, expected.unwrap());
+    assert_eq!(u128::try_from(&max), Ok(u128::MAX));
+
+    let values = [i128::MIN, i128::MAX, -1, 0, 1 << 64, -(1 << 64) - 1, -12345678901234567890123];
+    for &x in &values {
+        assert_eq!(i128::try_from(&From::<i128>::from(x)), Ok(x));
+    }
+
+    let (x, t) = Mpfr::from_u128_ternary(64, (1 << 100) + 1, Round::Up);
+    assert_eq!(t, Ordering::Greater);
+    assert_eq!(x, (Mpfr::from_u64_ternary(64, 1, Round::Nearest).0 << 100).next_up());
+    let (y, t) = Mpfr::from_i128_ternary(64, -(1 << 100) - 1, Round::Up);
+    assert_eq!(t, Ordering::Greater);
+    assert_eq!(y, -Mpfr::new_u64_2exp(1, 100));
+}
+
+#[test]
+fn test_to_128() {
+    let x = Mpfr::new2_from_str(200, "-12345678901234567890123456789.5", 10).unwrap();
+    assert_eq!(x.to_i128_round(Round::Down), -12345678901234567890123456790);
+    assert_eq!(x.to_i128_round(Round::Zero), -12345678901234567890123456789);
+    assert_eq!((-&x).to_u128_round(Round::Up), 12345678901234567890123456790);
+
+    assert_eq!(Mpfr::inf(1).to_i128_round(Round::Nearest), i128::MAX);
+    assert_eq!(Mpfr::inf(-1).to_i128_round(Round::Nearest), i128::MIN);
+    assert_eq!(x.to_u128_round(Round::Nearest), 0);
+    assert_eq!(Mpfr::new_u64_2exp(1, 200).to_u128_round(Round::Nearest), u128::MAX);
+    assert_eq!(Mpfr::nan().to_i128_round(Round::Nearest), 0);
+}
+
+#[test]
+fn test_small_conversions() {
+    assert_eq!(Mpfr::from(-7i8), From::<i64>::from(-7));
+    assert_eq!(Mpfr::from(200u8), From::<i64>::from(200));
+    assert_eq!(Mpfr::from(i16::MIN), From::<i64>::from(-32768));
+    assert_eq!(Mpfr::from(u16::MAX), From::<i64>::from(65535));
+    assert_eq!(Mpfr::from(i32::MIN), From::<i64>::from(i32::MIN as i64));
+    assert_eq!(Mpfr::from(u32::MAX), From::<u64>::from(u32::MAX as u64));
+
+    let third: Mpfr = From::<f32>::from(1.0f32 / 3.0);
+    assert_eq!(Into::<f32>::into(&third), 1.0f32 / 3.0);
+    assert_eq!(Into::<f64>::into(&third), (1.0f32 / 3.0) as f64);
+
+    let x: Mpfr = From::<f64>::from(1.0 / 3.0);
+    assert_eq!(x.to_f32_round(Round::Down), 0.3333333f32);
+    assert_eq!(x.to_f32_round(Round::Up), 0.33333334f32);
+    assert!(Mpfr::from_f32_ternary(10, 0.1, Round::Nearest).1 != Ordering::Equal);
+}
+
+#[test]
+fn test_to_mpq() {
+    let x: Mpfr = From::<f64>::from(-0.375);
+    let q = Mpq::try_from(&x).unwrap();
+    assert_eq!(q, Mpq::ratio(&From::<i64>::from(-3), &From::<i64>::from(8)));
+
+    // All 200 bits survive, unlike through Mpf or f64
+    let one = Mpfr::new2_from_str(200, "1", 10).unwrap();
+    let third = &one / 3;
+    let exact = third.to_mpq().unwrap();
+    let (back, t) = Mpfr::from_mpq_ternary(200, &exact, Round::Nearest);
+    assert_eq!(back, third);
+    assert_eq!(t, Ordering::Equal);
+    assert!(Mpfr::from_mpq_ternary(300, &exact, Round::Nearest).0 == third);
+
+    assert_eq!(Mpfr::zero(-1).to_mpq(), Some(Mpq::zero()));
+    assert!(Mpfr::nan().to_mpq().is_none());
+    assert!(Mpfr::inf(1).to_mpq().is_none());
+    assert_eq!(Mpq::try_from(&Mpfr::nan()), Err(TryFromMpfrError::Nan));
+    assert_eq!(Mpq::try_from(&Mpfr::inf(-1)), Err(TryFromMpfrError::Overflow));
+}
+
+#[test]
+fn test_to_integer_exp() {
+    let x: Mpfr = From::<f64>::from(-0.375);
+    let (m, e) = x.to_integer_exp().unwrap();
+    assert_eq!(Mpfr::new_mpz_2exp(&m, e as i32), x);
+
+    let big = Mpfr::new2_from_str(100, "1e100", 10).unwrap();
+    let (m, e) = big.to_integer_exp().unwrap();
+    assert_eq!(Mpfr::from_mpz_ternary(100, &m, Round::Nearest).0 << e, big);
+
+    assert_eq!(Mpfr::zero(1).to_integer_exp(), Some((Mpz::new(), 0)));
+    assert_eq!(Mpfr::nan().to_integer_exp(), None);
+    assert_eq!(Mpfr::inf(-1).to_integer_exp(), None);
+}
+
 #[test]
 fn test_abs() {
     let a: Mpfr = From::<i64>::from(1);
@@ -387,3 +1293,94 @@ fn test_rustc_serialize() {
     let b: Test = json::decode("{\"price\": \"0.75\"}").unwrap();
     assert!(a == b);
 }
+
+#[test]
+fn test_flags() {
+    let one: Mpfr = From::<i64>::from(1);
+    let three: Mpfr = From::<i64>::from(3);
+
+    let (_, flags) = Mpfr::with_flags(|| &one + &three);
+    assert!(flags.is_empty());
+
+    let (_, flags) = Mpfr::with_flags(|| &one / &three);
+    assert!(flags.inexact());
+    assert!(!flags.overflow() && !flags.nan());
+
+    let nan = Mpfr::nan();
+    let (_, flags) = Mpfr::with_flags(|| nan == one);
+    assert!(flags.is_empty());
+
+    let (_, flags) = Mpfr::with_flags(|| (-&one).sqrt());
+    assert_eq!(flags, Flags::NAN);
+}
+
+#[test]
+fn test_flags_save_restore() {
+    let saved = Flags::save();
+
+    Flags::clear_all();
+    Flags::raise(Flags::OVERFLOW | Flags::DIVBY0);
+    assert_eq!(Flags::test(Flags::OVERFLOW | Flags::NAN), Flags::OVERFLOW);
+    Flags::clear(Flags::OVERFLOW);
+    assert_eq!(Flags::save(), Flags::DIVBY0);
+
+    let (_, flags) = Mpfr::with_flags(|| Flags::raise(Flags::UNDERFLOW));
+    assert_eq!(flags, Flags::UNDERFLOW);
+    assert_eq!(Flags::save(), Flags::DIVBY0 | Flags::UNDERFLOW);
+
+    saved.restore();
+    assert_eq!(Flags::save(), saved);
+}
+
+#[test]
+fn test_flags_unwind() {
+    Flags::clear_all();
+    Flags::raise(Flags::ERANGE);
+    let res = panic::catch_unwind(|| {
+        Mpfr::with_flags(|| {
+            Flags::raise(Flags::INEXACT);
+            panic!("unwinding through with_flags");
+        })
+    });
+    assert!(res.is_err());
+    assert_eq!(Flags::save(), Flags::ERANGE | Flags::INEXACT);
+    Flags::clear_all();
+}
+
+#[test]
+fn test_exp_range() {
+    let (emin, emax) = (Mpfr::get_emin(), Mpfr::get_emax());
+    {
+        let _range = ExpRange::new(-10, 10).unwrap();
+        assert_eq!(Mpfr::get_emin(), -10);
+        assert_eq!(Mpfr::get_emax(), 10);
+        let _inner = ExpRange::new(-5, 5).unwrap();
+        assert_eq!(Mpfr::get_emax(), 5);
+    }
+    assert_eq!(Mpfr::get_emin(), emin);
+    assert_eq!(Mpfr::get_emax(), emax);
+    assert!(ExpRange::new(0, i64::MAX).is_none());
+    assert!(ExpRange::new(10, -10).is_none());
+    assert_eq!(Mpfr::get_emin(), emin);
+    assert_eq!(Mpfr::get_emax(), emax);
+}
+
+#[test]
+fn test_ieee_emulation() {
+    let _range = ExpRange::ieee(IeeeFormat::Binary32);
+    let prec = IeeeFormat::Binary32.precision();
+
+    let (max, _) = Mpfr::from_f64_ternary(prec, f32::MAX as f64, Round::Nearest);
+    let (twice, _) = max.mul_ternary(&From::<i64>::from(2), Round::Nearest);
+    assert!(twice == Mpfr::inf(1));
+
+    let min_subnormal = f32::from_bits(1) as f64;
+    let (mut x, t) = Mpfr::from_f64_ternary(prec, min_subnormal * 0.75, Round::Nearest);
+    let t = x.subnormalize(t, Round::Nearest);
+    assert!(x == From::<f64>::from(min_subnormal));
+    assert_eq!(t, ::std::cmp::Ordering::Greater);
+
+    let (mut y, t) = Mpfr::from_f64_ternary(prec, 1.5 * min_subnormal, Round::Nearest);
+    y.subnormalize(t, Round::Nearest);
+    assert!(y == From::<f64>::from(2.0 * min_subnormal));
+}